@@ -213,3 +213,98 @@ fn test_burn_it() {
 
 */
 }
+
+#[test]
+fn test_show_xrd() {
+    // Set up environment.
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor.publish_package(include_package!("fixburn1")).unwrap();
+
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "FixBurn", "new", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    assert!(receipt.result.is_ok());
+    let component_addr = receipt.new_component_addresses[0];
+    let flam_addr = receipt.new_resource_addresses[2];
+
+    // ProofOf<XRD> works as an argument with or without runtime checks, XRD is declared with an address
+    let transaction = TransactionBuilder::new()
+        .create_proof_from_account(RADIX_TOKEN, account)
+        .pop_from_auth_zone(|builder, proof_id| {
+            builder.call_method(
+                component_addr,
+                "show_xrd",
+                vec![
+                    scrypto_encode(&scrypto::resource::Proof(proof_id))
+                ]
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+
+    // and fails to decode a proof of the wrong resource, also with or without runtime checks
+    let transaction = TransactionBuilder::new()
+        .create_proof_from_account(flam_addr, account)
+        .pop_from_auth_zone(|builder, proof_id| {
+            builder.call_method(
+                component_addr,
+                "show_xrd",
+                vec![
+                    scrypto_encode(&scrypto::resource::Proof(proof_id))
+                ]
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(!receipt.result.is_ok());
+}
+
+#[test]
+fn test_burn_it_requires_auth() {
+    // Set up environment.
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor.publish_package(include_package!("fixburn1")).unwrap();
+
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "FixBurn", "new", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    assert!(receipt.result.is_ok());
+    let component_addr = receipt.new_component_addresses[0];
+    let flam_addr = receipt.new_resource_addresses[2];
+
+    // the burn_it rule refers to the auth_def field with field_of!, so without a proof of AUTH the call is rejected
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!(10), flam_addr, account)
+        .take_from_worktop_by_amount(dec!(10), flam_addr, |builder, bucket_id| {
+            builder.call_method(
+                component_addr,
+                "burn_it",
+                vec![
+                    scrypto_encode(&scrypto::resource::Bucket(bucket_id))
+                ]
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(!receipt.result.is_ok());
+}
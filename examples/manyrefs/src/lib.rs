@@ -50,9 +50,9 @@ blueprint! {
         pub fn double_tokens_unwrapped(&mut self, auth: ProofOf<T>) -> Bucket {
             self.vault.take(auth.amount() * 2).unwrap()
         }
-        // only checking on auth, since Q isn't in self, any passed in resource will be created as Q.  Any resource could be used EXCEPT another declared one that is in self (such as T) *DANGEROUS*
+        // only checking on auth, since Q isn't in self, any passed in resource will be created as Q.  Any resource could be used EXCEPT another declared one that is in self (such as T) *DANGEROUS* (unless scrypto_statictypes/trusted_bindings is enabled, then Q can't be bound from an argument)
         pub fn double_tokens_q_unwrapped(&mut self, auth: ProofOf<Q>) -> Bucket {
-            self.vault.take(auth.amount() * 2).unwrap()
+            self.vault.take(auth.amount().convert::<T>() * 2).unwrap() // the amount of Q must be converted explicitly to take T
         }
 
         // tests using Proofs and ProofOfs
@@ -71,12 +71,12 @@ blueprint! {
         }
         // ProofOf has Drop so this concise implementation is possible
         pub fn check_amount_new(&self, auth: ProofOf<T>, a: Decimal) -> bool {
-            self.mirror_new(auth).amount() >= a
+            self.mirror_new(auth).amount() >= a.into()
         }
         // must use check_amount_old style, or implement explicit drop when calling mirror_old in the same way
         pub fn check_vault_amount_old(&mut self, a: Decimal) -> bool {
             let bucketof: BucketOf<T> = self.vault.take(a);
-            let bucket: &Bucket = &bucketof;
+            let bucket: &Bucket = bucketof.as_ref();
             let auth = bucket.create_proof();
             //let r = self.mirror_old(auth).amount() >= a; // can't use this would need the same br.drop() as in check_amount_old
             let r = self.check_amount_old(auth, a);
@@ -89,7 +89,7 @@ blueprint! {
             let bucket: BucketOf<T> = self.vault.take(a);
             //let bucket: &Bucket = &bucket;
             let auth = bucket.create_proof();
-            let r = self.mirror_new(auth).amount() >= a;
+            let r = self.mirror_new(auth).amount() >= a.into();
             //let r = self.check_amount_new(auth, a);
             self.vault.put(bucket);
             r
@@ -100,7 +100,7 @@ blueprint! {
             let bucket: BucketOf<T> = self.vault.take(a);
             //let bucket: &Bucket = &bucket;
             let auth = bucket.create_proof();
-            //let r = self.mirror_new(auth).amount() >= a;
+            //let r = self.mirror_new(auth).amount() >= a.into();
             let r = self.check_amount_new(auth, a);
             self.vault.put(bucket);
             r
@@ -110,7 +110,7 @@ blueprint! {
         pub fn bad_proof_old(&mut self, a: Decimal) -> Proof {
             let bucket: Bucket = self.vault.take(a).unwrap();
             let bref = bucket.create_proof();
-            let old_vault: &mut Vault = &mut self.vault;
+            let old_vault: &mut Vault = self.vault.as_mut();
             old_vault.put(bucket);
             bref
         }
@@ -0,0 +1,50 @@
+use scrypto::prelude::*;
+use scrypto_statictypes::prelude::*;
+
+#[derive(NonFungibleData)]
+pub struct AccountNftData {
+    number: u32,
+}
+
+declare_resource!(ACCOUNT_NFT, non_fungible = AccountNftData); // only bound by creating it, so with runtime_typechecks
+
+blueprint! {
+    struct Accounts {}
+
+    impl Accounts {
+        // creates an account anyone can withdraw from and checks the typed methods of the Account proxy with it
+        pub fn check_account(xrd: BucketOf<XRD>) -> ComponentAddress {
+            let address = call_function_of(ACCOUNT_PACKAGE, "Account", "new", args![rule!(allow_all)]);
+            let account = Account::new(address);
+
+            let amount = xrd.amount();
+            let mut buckets = vec![xrd];
+            let half = buckets[0].take(amount / 2);
+            buckets.push(half);
+            account.deposit_batch_of(buckets);
+            assert!(account.balance_of::<XRD>() == amount);
+
+            let proof = account.create_proof_of::<XRD>();
+            assert!(proof.amount() == amount);
+            proof.drop();
+
+            // without runtime_typechecks ACCOUNT_NFT has no address, so these would panic with NoAddress
+            if cfg!(feature = "runtime_typechecks") {
+                let nfts = ResourceBuilderOf::<ACCOUNT_NFT>::new_non_fungible().initial_supply([
+                    (NonFungibleId::from_u32(1), AccountNftData { number: 1 }),
+                    (NonFungibleId::from_u32(2), AccountNftData { number: 2 }),
+                ]);
+                account.deposit_batch_of(vec![nfts]);
+                assert!(account.balance_of::<ACCOUNT_NFT>() == AmountOf::new(2));
+
+                let ids = BTreeSet::from([NonFungibleId::from_u32(2)]);
+                let bucket = account.withdraw_non_fungibles_of::<ACCOUNT_NFT>(&ids);
+                assert_eq!(bucket.non_fungible_ids(), ids);
+                assert_eq!(bucket.non_fungible_data()[&NonFungibleId::from_u32(2)].number, 2);
+                assert!(account.balance_of::<ACCOUNT_NFT>() == AmountOf::new(1));
+                account.deposit_of::<ACCOUNT_NFT>(bucket);
+            }
+            address
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use scrypto::prelude::*;
+use scrypto_statictypes::prelude::*;
+
+use crate::declarations::NOT_XRD;
+
+#[cfg(feature = "runtime_typechecks")]
+#[derive(NonFungibleData)]
+pub struct ZoneBadgeData {
+    number: u32,
+}
+
+// only bound by creating it, without runtime_typechecks it has no address to create proofs with
+#[cfg(feature = "runtime_typechecks")]
+declare_resource!(ZONE_BADGE, non_fungible = ZoneBadgeData);
+
+blueprint! {
+    struct AuthZones {}
+
+    impl AuthZones {
+        // gives back an XRD bucket after checking the typed auth zone operations with proofs of it
+        pub fn check_auth_zone(xrd: BucketOf<XRD>) -> BucketOf<XRD> {
+            let amount = xrd.amount();
+            AuthZoneOf::push(xrd.create_proof());
+
+            let proof = AuthZoneOf::create_proof_of::<XRD>();
+            assert!(proof.amount() == amount);
+            proof.drop();
+            let proof = AuthZoneOf::create_proof_by_amount_of::<XRD, _>(amount / 2);
+            assert!(proof.amount() == amount / 2);
+            proof.drop();
+
+            // the proof on top is of XRD, so popping it as NOT_XRD gives it back (with and without runtime_typechecks)
+            let proof = match AuthZoneOf::try_pop_of::<NOT_XRD>() {
+                Ok(_) => panic!("NOT_XRD address not checked"),
+                Err(ConversionError { value, error }) => {
+                    assert!(matches!(error, StaticTypeError::Mismatch { .. }), "unexpected error: {}", error);
+                    value
+                }
+            };
+            AuthZone::push(proof);
+            let proof = AuthZoneOf::pop_of::<XRD>();
+            assert!(proof.amount() == amount);
+            proof.drop();
+
+            #[cfg(feature = "runtime_typechecks")]
+            {
+                let badges = ResourceBuilderOf::<ZONE_BADGE>::new_non_fungible()
+                    .burnable(rule!(allow_all), LOCKED)
+                    .initial_supply([
+                        (NonFungibleId::from_u32(1), ZoneBadgeData { number: 1 }),
+                        (NonFungibleId::from_u32(2), ZoneBadgeData { number: 2 }),
+                    ]);
+                AuthZoneOf::push(badges.create_proof());
+                let ids = BTreeSet::from([NonFungibleId::from_u32(2)]);
+                let proof = AuthZoneOf::create_proof_by_ids_of::<ZONE_BADGE>(&ids);
+                assert_eq!(proof.non_fungible_ids(), ids);
+                assert_eq!(proof.non_fungible_data()[&NonFungibleId::from_u32(2)].number, 2);
+                proof.drop();
+                AuthZoneOf::pop_of::<ZONE_BADGE>().drop();
+                badges.burn();
+            }
+            xrd
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use scrypto::prelude::*;
+use scrypto_statictypes::prelude::*;
+use scrypto_statictypes::registry;
+
+declare_resource!(STATE_TOKEN, fungible);
+declare_resource!(STATE_AUTH, non_fungible); // only referenced with a ResourceOf in the component state
+declare_resource!(CALLER_TOKEN);
+
+blueprint! {
+    struct Bindings {
+        token_vault: VaultOf<STATE_TOKEN>,
+        auth_def: ResourceOf<STATE_AUTH>,
+    }
+
+    impl Bindings {
+        pub fn new() -> ComponentAddress {
+            let tokens = ResourceBuilderOf::<STATE_TOKEN>::new_fungible().initial_supply(100);
+            let auth_def = ResourceBuilderOf::<STATE_AUTH>::new_non_fungible().no_initial_supply();
+            Self {
+                token_vault: VaultOf::with_bucket(tokens),
+                auth_def,
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        // checks what decoding the component state bound, each call starts without any bindings
+        pub fn check_state(&self) {
+            assert!(self.token_vault.amount() == AmountOf::new(100));
+            if cfg!(feature = "runtime_typechecks") {
+                // the decoded vault is from the component state, a trusted source
+                let token = registry::bound_address::<STATE_TOKEN>();
+                assert!(token.map_or(false, |address| self.token_vault.resource_address() == address));
+            }
+            let auth = registry::bound_address::<STATE_AUTH>();
+            if cfg!(feature = "trusted_bindings") {
+                // the decoded ResourceOf may as well be an argument, so it decodes without binding
+                assert!(auth.is_none(), "ResourceOf bound from decoding");
+            } else if cfg!(feature = "runtime_typechecks") {
+                assert!(auth.map_or(false, |address| self.auth_def == address));
+            }
+        }
+    }
+}
+
+blueprint! {
+    struct CallerVault {
+        vault: Option<VaultOf<CALLER_TOKEN>>,
+        rejected: Option<Vault>,
+    }
+
+    impl CallerVault {
+        // a new vault is not from the component state, so with trusted_bindings the caller's resource doesn't bind
+        pub fn new(resource_address: ResourceAddress) -> ComponentAddress {
+            let result: Result<VaultOf<CALLER_TOKEN>, _> = Vault::new(resource_address).try_into_of();
+            let (vault, rejected) = match result {
+                Ok(vault) => {
+                    assert!(!cfg!(feature = "trusted_bindings"), "VaultOf bound to an untrusted address");
+                    (Some(vault), None)
+                }
+                Err(ConversionError { value, error }) => {
+                    assert!(cfg!(feature = "trusted_bindings"), "unexpected error: {}", error);
+                    assert!(matches!(error, StaticTypeError::Unbound { .. }), "unexpected error: {}", error);
+                    (None, Some(value))
+                }
+            };
+            Self { vault, rejected }.instantiate().globalize()
+        }
+
+        // the same with the panicking VaultOf::new, which fails with trusted_bindings
+        pub fn with_vault_of(resource_address: ResourceAddress) -> ComponentAddress {
+            Self {
+                vault: Some(VaultOf::new(resource_address)),
+                rejected: None,
+            }
+            .instantiate()
+            .globalize()
+        }
+    }
+}
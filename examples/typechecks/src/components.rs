@@ -0,0 +1,38 @@
+use scrypto::prelude::*;
+use scrypto_statictypes::prelude::*;
+
+declare_blueprint!(SWAPPER, "Swapper");
+// another blueprint of this package
+declare_blueprint!(TRADER, "Trader");
+// the right name in the wrong package
+declare_blueprint!(ACCOUNT_SWAPPER, "Swapper", package = ACCOUNT_PACKAGE);
+// nothing declared, so nothing to check
+declare_blueprint!(ANY_BLUEPRINT);
+
+blueprint! {
+    struct Components {}
+
+    impl Components {
+        // the swapper is checked when decoded as a ComponentOf<SWAPPER>, then against the other declarations
+        pub fn check_swapper(swapper: ComponentOf<SWAPPER>) {
+            assert!(swapper.call::<Decimal>("price", vec![]) == 2.into());
+            let address = swapper.unwrap();
+            let result: Result<ComponentOf<ANY_BLUEPRINT>, _> = address.try_into_of();
+            assert!(result.map_or(false, |any| any == address));
+            let result: Result<ComponentOf<TRADER>, _> = address.try_into_of();
+            Self::expect_mismatch(result, "blueprint name");
+            let result: Result<ComponentOf<ACCOUNT_SWAPPER>, _> = address.try_into_of();
+            Self::expect_mismatch(result, "package");
+        }
+
+        fn expect_mismatch<W>(result: Result<W, ConversionError<ComponentAddress>>, expected: &str) {
+            match result {
+                Ok(_) => assert!(!cfg!(feature = "runtime_typechecks"), "{} not checked", expected),
+                Err(ConversionError { error, .. }) => match error {
+                    StaticTypeError::BlueprintMismatch { field, .. } => assert_eq!(field, expected),
+                    _ => panic!("unexpected error: {}", error),
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use scrypto::prelude::*;
+use scrypto_statictypes::prelude::*;
+
+// XRD declared with the wrong kind, rejected with runtime_typechecks when the declared address is first used
+declare_resource!(XRD_AS_BADGE, RADIX_TOKEN, non_fungible);
+// a second declaration of XRD's address, rejected with runtime_typechecks when used after XRD
+declare_resource!(XRD_DUPLICATE, RADIX_TOKEN);
+// an intentional alias, the same type as XRD
+declare_resource_alias!(WXRD = XRD);
+// any address other than XRD's, compared with and without runtime_typechecks
+declare_resource!(NOT_XRD, "030102030405060708090a0b0c0d0e0f101112131415161718191a");
+
+blueprint! {
+    struct Declarations {}
+
+    impl Declarations {
+        // gives back an XRD bucket after checking the conversion to XRD_AS_BADGE is rejected (every time)
+        pub fn wrong_kind(bucket: Bucket) -> Bucket {
+            let bucket = Self::expect_kind_mismatch(bucket);
+            Self::expect_kind_mismatch(bucket)
+        }
+
+        // gives back an XRD bucket after checking the conversion to XRD_DUPLICATE is rejected, but not to the alias WXRD
+        pub fn duplicate_address(bucket: Bucket) -> Bucket {
+            let xrd: BucketOf<XRD> = bucket.try_into_of().unwrap_or_else(|e| panic!("XRD rejected: {}", e));
+            let wxrd: BucketOf<WXRD> = xrd;
+            let result: Result<BucketOf<XRD_DUPLICATE>, _> = wxrd.unwrap().try_into_of();
+            match result {
+                Ok(bucket) => {
+                    assert!(!cfg!(feature = "runtime_typechecks"), "XRD_DUPLICATE address not checked");
+                    bucket.unwrap()
+                }
+                Err(ConversionError { value, error }) => {
+                    assert!(matches!(error, StaticTypeError::AddressAlreadyBound { .. }), "unexpected error: {}", error);
+                    value
+                }
+            }
+        }
+
+        // gives back an XRD bucket after checking the conversions to NOT_XRD are rejected
+        pub fn wrong_address(bucket: Bucket) -> Bucket {
+            let result: Result<BucketOf<NOT_XRD>, _> = bucket.try_into_of();
+            let bucket = Self::expect_mismatch(result);
+            let address = bucket.resource_address();
+            let result: Result<ResourceOf<NOT_XRD>, _> = address.try_into_of();
+            Self::expect_mismatch(result);
+            let result: Result<ProofOf<NOT_XRD>, _> = bucket.create_proof().try_into_of();
+            Self::expect_mismatch(result).drop();
+            bucket
+        }
+
+        fn expect_mismatch<T, W>(result: Result<W, ConversionError<T>>) -> T {
+            match result {
+                Ok(_) => panic!("NOT_XRD address not checked"),
+                Err(ConversionError { value, error }) => {
+                    assert!(matches!(error, StaticTypeError::Mismatch { .. }), "unexpected error: {}", error);
+                    value
+                }
+            }
+        }
+
+        fn expect_kind_mismatch(bucket: Bucket) -> Bucket {
+            let result: Result<BucketOf<XRD_AS_BADGE>, _> = bucket.try_into_of();
+            match result {
+                Ok(bucket) => {
+                    assert!(!cfg!(feature = "runtime_typechecks"), "XRD_AS_BADGE kind not checked");
+                    bucket.unwrap()
+                }
+                Err(ConversionError { value, error }) => {
+                    assert!(matches!(error, StaticTypeError::KindMismatch { .. }), "unexpected error: {}", error);
+                    value
+                }
+            }
+        }
+    }
+}
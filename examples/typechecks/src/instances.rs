@@ -0,0 +1,27 @@
+use scrypto::prelude::*;
+use scrypto_statictypes::prelude::*;
+
+declare_resource!(INSTANCE_TOKEN, fungible); // a different resource for each component
+
+blueprint! {
+    struct Instances {
+        tokens: VaultOf<INSTANCE_TOKEN>,
+    }
+
+    impl Instances {
+        // binds INSTANCE_TOKEN to a new resource in the scope of this call only
+        pub fn new() -> ComponentAddress {
+            let tokens = ResourceBuilderOf::<INSTANCE_TOKEN>::new_fungible().initial_supply(100);
+            Self {
+                tokens: VaultOf::with_bucket(tokens),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        // binds INSTANCE_TOKEN to the resource of this component, in its own scope
+        pub fn take(&mut self, amount: Decimal) -> BucketOf<INSTANCE_TOKEN> {
+            self.tokens.take(amount)
+        }
+    }
+}
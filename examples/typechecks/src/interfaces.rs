@@ -0,0 +1,82 @@
+use scrypto::prelude::*;
+use scrypto_statictypes::prelude::*;
+
+declare_resource!(SWAP_TOKEN, fungible);
+
+typed_interface! {
+    /// The methods of a Swapper component, as seen by its callers
+    pub trait Swap {
+        fn swap(&self, input: BucketOf<XRD>) -> BucketOf<SWAP_TOKEN>;
+        fn price(&self) -> Decimal;
+    }
+    pub struct SwapComponent;
+}
+
+typed_interface! {
+    /// A stub which expects SWAP_TOKEN back from a method returning XRD
+    pub trait WrongSwap {
+        fn swap_wrong(&self, input: BucketOf<XRD>) -> BucketOf<SWAP_TOKEN>;
+    }
+    pub struct WrongSwapComponent;
+}
+
+blueprint! {
+    struct Swapper {
+        tokens: VaultOf<SWAP_TOKEN>,
+        payments: VaultOf<XRD>,
+    }
+
+    impl Swapper {
+        pub fn new() -> ComponentAddress {
+            let tokens = ResourceBuilderOf::<SWAP_TOKEN>::new_fungible().initial_supply(1000);
+            Self {
+                tokens: VaultOf::with_bucket(tokens),
+                payments: VaultOf::new(RADIX_TOKEN),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn swap(&mut self, input: BucketOf<XRD>) -> BucketOf<SWAP_TOKEN> {
+            let amount = input.amount().convert::<SWAP_TOKEN>() * self.price();
+            self.payments.put(input);
+            self.tokens.take(amount)
+        }
+
+        pub fn price(&self) -> Decimal {
+            2.into()
+        }
+
+        // hands the payment back instead of SWAP_TOKEN
+        pub fn swap_wrong(&mut self, input: BucketOf<XRD>) -> BucketOf<XRD> {
+            input
+        }
+    }
+}
+
+blueprint! {
+    struct Trader {}
+
+    impl Trader {
+        // calls the Swapper through its typed stub, SWAP_TOKEN is bound to the address the caller expects
+        pub fn buy(swapper: ComponentAddress, token: ResourceAddress, payment: BucketOf<XRD>) -> BucketOf<SWAP_TOKEN> {
+            ResourceOf::<SWAP_TOKEN>::bind(token).unwrap_or_else(|e| panic!("SWAP_TOKEN not bound: {}", e));
+            let swapper: SwapComponent = swapper.into();
+            let expected = payment.amount().convert::<SWAP_TOKEN>() * swapper.price();
+            let tokens = swapper.swap(payment);
+            assert!(tokens.amount() == expected);
+            assert!(tokens.resource_address() == token);
+            tokens
+        }
+
+        // the returned XRD fails decoding as SWAP_TOKEN with runtime_typechecks, and is accepted unchecked without
+        pub fn buy_wrong(
+            swapper: ComponentAddress,
+            token: ResourceAddress,
+            payment: BucketOf<XRD>,
+        ) -> BucketOf<SWAP_TOKEN> {
+            ResourceOf::<SWAP_TOKEN>::bind(token).unwrap_or_else(|e| panic!("SWAP_TOKEN not bound: {}", e));
+            WrongSwapComponent::new(swapper).swap_wrong(payment)
+        }
+    }
+}
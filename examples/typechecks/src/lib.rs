@@ -0,0 +1,13 @@
+//! Blueprints exercising the checks of scrypto_statictypes, called by the tests in tests/lib.rs
+//!
+//! Each blueprint asserts the expected outcome of a check itself, so a test only has to verify the transaction succeeds
+//! (or fails, for the checks which panic).
+
+mod accounts;
+mod authzones;
+mod bindings;
+mod components;
+mod declarations;
+mod instances;
+mod interfaces;
+mod pairs;
@@ -0,0 +1,41 @@
+use scrypto::prelude::*;
+use scrypto_statictypes::prelude::*;
+use scrypto_statictypes::registry;
+
+declare_resource!(TOKEN_A);
+declare_resource!(TOKEN_B);
+// the LP token of each pair, so LP<TOKEN_A, TOKEN_B> and LP<TOKEN_B, TOKEN_A> are different resources
+declare_resource!(LP<A, B>, fungible);
+
+blueprint! {
+    struct Pairs {}
+
+    impl Pairs {
+        // creates the LP tokens of both orders of a pair and checks they stay distinct
+        pub fn distinct_lp_tokens() -> (BucketOf<LP<TOKEN_A, TOKEN_B>>, BucketOf<LP<TOKEN_B, TOKEN_A>>) {
+            let ab = ResourceBuilderOf::<LP<TOKEN_A, TOKEN_B>>::new_fungible().initial_supply(100);
+            let ba = ResourceBuilderOf::<LP<TOKEN_B, TOKEN_A>>::new_fungible().initial_supply(50);
+            assert_ne!(ab.resource_address(), ba.resource_address());
+            if cfg!(feature = "runtime_typechecks") {
+                // each parametrization is bound to its own address, keyed by the full type
+                assert_eq!(registry::bound_address::<LP<TOKEN_A, TOKEN_B>>(), Some(ab.resource_address()));
+                assert_eq!(registry::bound_address::<LP<TOKEN_B, TOKEN_A>>(), Some(ba.resource_address()));
+                assert_eq!(registry::bound_address::<LP<TOKEN_A, TOKEN_A>>(), None);
+            }
+
+            // the bucket of one order is rejected as the other
+            let result: Result<BucketOf<LP<TOKEN_B, TOKEN_A>>, _> = ab.unwrap().try_into_of();
+            let ab: BucketOf<LP<TOKEN_A, TOKEN_B>> = match result {
+                Ok(bucket) => {
+                    assert!(!cfg!(feature = "runtime_typechecks"), "LP<TOKEN_B, TOKEN_A> not checked");
+                    bucket.unwrap().unchecked_into()
+                }
+                Err(ConversionError { value, error }) => {
+                    assert!(matches!(error, StaticTypeError::Mismatch { .. }), "unexpected error: {}", error);
+                    value.try_into_of().unwrap()
+                }
+            };
+            (ab, ba)
+        }
+    }
+}
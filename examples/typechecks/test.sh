@@ -0,0 +1,14 @@
+#!/usr/bin/env sh
+set -e
+
+# test with compile time only features
+cargo build --target wasm32-unknown-unknown --release --no-default-features
+cargo test --release --no-default-features
+
+# test with default (runtime typechecks) features
+cargo build --target wasm32-unknown-unknown --release
+cargo test --release
+
+# test with trusted bindings
+cargo build --target wasm32-unknown-unknown --release --features trusted_bindings
+cargo test --release --features trusted_bindings
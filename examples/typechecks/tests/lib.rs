@@ -0,0 +1,321 @@
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+macro_rules! setup {
+    ($ledger:ident) => {{
+        // Set up environment.
+        let mut executor = TransactionExecutor::new(&mut $ledger, true);
+        let (pk, sk, account) = executor.new_account();
+        let package = executor.publish_package(include_package!("typechecks")).unwrap(); // include_package instead of compile_package so we can control feature flags for testing
+        (executor, pk, sk, account, package)
+    }};
+}
+
+#[test]
+fn test_wrong_kind_declaration() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    // the blueprint asserts the conversion to XRD_AS_BADGE fails with KindMismatch (with runtime_typechecks)
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!(10), RADIX_TOKEN, account)
+        .take_from_worktop_by_amount(dec!(10), RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "Declarations",
+                "wrong_kind",
+                vec![scrypto_encode(&scrypto::resource::Bucket(bucket_id))],
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+}
+
+#[test]
+fn test_distinct_lp_tokens() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    // the blueprint asserts LP<TOKEN_A, TOKEN_B> and LP<TOKEN_B, TOKEN_A> are bound to their own addresses
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Pairs", "distinct_lp_tokens", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+    assert_eq!(receipt.new_resource_addresses.len(), 2);
+}
+
+#[test]
+fn test_wrong_address_declaration() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    // the blueprint asserts the conversions to NOT_XRD fail with Mismatch (with and without runtime_typechecks)
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!(10), RADIX_TOKEN, account)
+        .take_from_worktop_by_amount(dec!(10), RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "Declarations",
+                "wrong_address",
+                vec![scrypto_encode(&scrypto::resource::Bucket(bucket_id))],
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+}
+
+#[test]
+fn test_state_bindings() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Bindings", "new", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+    let component = receipt.new_component_addresses[0];
+
+    // the blueprint asserts the decoded VaultOf binds and the decoded ResourceOf only binds without trusted_bindings
+    let transaction = TransactionBuilder::new()
+        .call_method(component, "check_state", vec![])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+}
+
+#[test]
+fn test_new_vault_binding() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, _account, package) = setup!(ledger);
+
+    // the blueprint asserts the conversion only fails (with Unbound) with trusted_bindings
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "CallerVault", "new", vec![scrypto_encode(&RADIX_TOKEN)])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+
+    // VaultOf::new panics instead
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "CallerVault", "with_vault_of", vec![scrypto_encode(&RADIX_TOKEN)])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert_eq!(receipt.result.is_ok(), !cfg!(feature = "trusted_bindings"));
+}
+
+#[test]
+fn test_two_instances() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    // two instantiations in one transaction, each binding INSTANCE_TOKEN to its own resource
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Instances", "new", vec![])
+        .call_function(package, "Instances", "new", vec![])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+    assert_eq!(receipt.new_component_addresses.len(), 2);
+    assert_eq!(receipt.new_resource_addresses.len(), 2);
+    assert_ne!(receipt.new_resource_addresses[0], receipt.new_resource_addresses[1]);
+
+    // and both components called in one transaction
+    let transaction = TransactionBuilder::new()
+        .call_method(receipt.new_component_addresses[0], "take", vec![scrypto_encode(&dec!(10))])
+        .call_method(receipt.new_component_addresses[1], "take", vec![scrypto_encode(&dec!(20))])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+}
+
+#[test]
+fn test_duplicate_address_declaration() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    // the blueprint asserts the conversion to XRD_DUPLICATE fails with AddressAlreadyBound (with runtime_typechecks)
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!(10), RADIX_TOKEN, account)
+        .take_from_worktop_by_amount(dec!(10), RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "Declarations",
+                "duplicate_address",
+                vec![scrypto_encode(&scrypto::resource::Bucket(bucket_id))],
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+}
+
+#[test]
+fn test_account_methods() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    // the blueprint asserts the balances, proof and withdrawn non-fungibles of a new account
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!(10), RADIX_TOKEN, account)
+        .take_from_worktop_by_amount(dec!(10), RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "Accounts",
+                "check_account",
+                vec![scrypto_encode(&scrypto::resource::Bucket(bucket_id))],
+            )
+        })
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+    assert_eq!(receipt.new_component_addresses.len(), 1);
+}
+
+#[test]
+fn test_typed_interface() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Swapper", "new", vec![])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+    let (swapper, token) = (receipt.new_component_addresses[0], receipt.new_resource_addresses[0]);
+
+    // the blueprint asserts the amount and resource returned through the stub
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!(10), RADIX_TOKEN, account)
+        .take_from_worktop_by_amount(dec!(10), RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "Trader",
+                "buy",
+                vec![
+                    scrypto_encode(&swapper),
+                    scrypto_encode(&token),
+                    scrypto_encode(&scrypto::resource::Bucket(bucket_id)),
+                ],
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+
+    // a stub expecting the wrong resource back only fails with runtime_typechecks, SWAP_TOKEN has no declared address
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!(10), RADIX_TOKEN, account)
+        .take_from_worktop_by_amount(dec!(10), RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "Trader",
+                "buy_wrong",
+                vec![
+                    scrypto_encode(&swapper),
+                    scrypto_encode(&token),
+                    scrypto_encode(&scrypto::resource::Bucket(bucket_id)),
+                ],
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert_eq!(receipt.result.is_ok(), !cfg!(feature = "runtime_typechecks"));
+}
+
+#[test]
+fn test_component_of() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Swapper", "new", vec![])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+    let swapper = receipt.new_component_addresses[0];
+
+    // the blueprint asserts the conversions to the blueprints the swapper is not fail with BlueprintMismatch
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Components", "check_swapper", vec![scrypto_encode(&swapper)])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+
+    // an account is not a Swapper, so decoding the argument fails with runtime_typechecks (and calling price without)
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Components", "check_swapper", vec![scrypto_encode(&account)])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_err());
+}
+
+#[test]
+fn test_auth_zone() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut executor, pk, sk, account, package) = setup!(ledger);
+
+    // the blueprint asserts the proofs from the auth zone, and that popping the wrong resource fails with Mismatch
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!(10), RADIX_TOKEN, account)
+        .take_from_worktop_by_amount(dec!(10), RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "AuthZones",
+                "check_auth_zone",
+                vec![scrypto_encode(&scrypto::resource::Bucket(bucket_id))],
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    println!("{:?}\n", receipt);
+    assert!(receipt.result.is_ok());
+}
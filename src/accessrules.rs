@@ -0,0 +1,102 @@
+use std::marker::PhantomData;
+
+use scrypto::prelude::{require, require_amount, ProofRule, SoftResource, SoftResourceOrNonFungible};
+
+use crate::amountof::*;
+use crate::internal::*;
+use crate::resourceof::ResourceOf;
+
+/// A reference to a component field holding a `ResourceOf<RES>`, for access rules resolved when the method is called
+///
+/// Created with `field_of!(Self, auth_def)`, which only compiles while `Self` has a field `auth_def` of type
+/// `ResourceOf<_>`, so renaming the field or changing its resource is a compile error instead of an auth failure.
+/// Use it in place of the field name string, ie. `rule!(require_of(field_of!(Self, auth_def)))` instead of
+/// `rule!(require("auth_def"))`.
+pub struct FieldOf<RES: Resource> {
+    path: &'static str,
+    phantom: PhantomData<RES>,
+}
+
+impl<RES: Resource> FieldOf<RES> {
+    // only for field_of!, the accessor is never called, it only makes the compiler check the field and its type
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn of<S>(_field: fn(&S) -> &ResourceOf<RES>, path: &'static str) -> Self {
+        Self {
+            path,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the name of the field.
+    #[inline(always)]
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+}
+
+// manual impls instead of derive, since derive would require the same traits on the (uninhabited) RES
+
+impl<RES: Resource> Clone for FieldOf<RES> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<RES: Resource> Copy for FieldOf<RES> {}
+
+impl<RES: Resource> std::fmt::Debug for FieldOf<RES> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FieldOf<{}>({})", std::any::type_name::<RES>(), self.path)
+    }
+}
+
+/// Anything an access rule can require for a resource `RES`: a `&ResourceOf<RES>` or a `FieldOf<RES>`
+pub trait ResourceRuleOf<RES: Resource> {
+    fn soft_resource(self) -> SoftResource;
+    fn soft_resource_or_non_fungible(self) -> SoftResourceOrNonFungible;
+}
+
+impl<RES: Resource> ResourceRuleOf<RES> for &ResourceOf<RES> {
+    #[inline(always)]
+    fn soft_resource(self) -> SoftResource {
+        self._resource_address().into()
+    }
+
+    #[inline(always)]
+    fn soft_resource_or_non_fungible(self) -> SoftResourceOrNonFungible {
+        self._resource_address().into()
+    }
+}
+
+impl<RES: Resource> ResourceRuleOf<RES> for FieldOf<RES> {
+    #[inline(always)]
+    fn soft_resource(self) -> SoftResource {
+        self.path.into()
+    }
+
+    #[inline(always)]
+    fn soft_resource_or_non_fungible(self) -> SoftResourceOrNonFungible {
+        self.path.into()
+    }
+}
+
+/// Requires a proof of the resource, the typed `require`
+///
+/// Use inside `rule!` like `require`, ie. `rule!(require_of(&badge) || require_of(field_of!(Self, admin_def)))`.
+#[inline(always)]
+pub fn require_of<RES: Resource, R: ResourceRuleOf<RES>>(resource: R) -> ProofRule {
+    require(resource.soft_resource_or_non_fungible())
+}
+
+/// Requires a proof of at least an amount of the resource, the typed `require_amount`
+///
+/// Use inside `rule!` like `require_amount`, ie. `rule!(require_amount_of(2, &badge))`.
+#[inline(always)]
+pub fn require_amount_of<RES: Resource, A: IntoAmountOf<RES>, R: ResourceRuleOf<RES>>(
+    amount: A,
+    resource: R,
+) -> ProofRule {
+    require_amount(amount.into_amount_of().unwrap(), resource.soft_resource())
+}
@@ -0,0 +1,474 @@
+//! Compile-time parsing of resource addresses
+//!
+//! Used by `declare_resource!(USDC, "resource_sim1...")` so a typo in an address is a compile error instead of a
+//! runtime mismatch.  Everything here is a `const fn` which panics (at compile time when used in a `const`) on any
+//! invalid input.
+//!
+//! Both the hex string form of Scrypto v0.4.1 (`ResourceAddress`'s `Display`, ie. `RADIX_TOKEN` is
+//! `030000000000000000000000000000000000000000000000000004`) and the human readable bech32m form are accepted.  A bech32m
+//! address names its network in the human readable part, which must be the `NETWORK` selected at build time.
+//!
+//! Only the hex form is what Scrypto v0.4.1 itself prints and parses (`ResourceAddress::from_str`, which the tests check
+//! this parser against), the bech32m form is the one of later Scrypto versions and is accepted so declarations don't
+//! have to change when upgrading.
+//!
+//! Per-network addresses (`declare_resource!(USDC, local = "...", mainnet = "...")`) are selected here too, by the
+//! `NETWORK` chosen at build time.
+
+use scrypto::prelude::ResourceAddress;
+
+/// Prefix of the human readable part of every resource address, the rest identifies the network
+pub const RESOURCE_HRP_PREFIX: &str = "resource_";
+
+// entity type byte written before the 26 bytes of a ResourceAddress in its hex string form, the bech32m form encodes the
+// same 27 bytes
+const ENTITY_TYPE_RESOURCE: u8 = 0x03;
+
+// entity type byte + 26 byte address
+const RESOURCE_ADDRESS_LENGTH: usize = 27;
+
+// the layout of ResourceAddress is only known to resource_address_from_bytes and resource_address_bytes
+
+const fn resource_address_from_bytes(bytes: &[u8; RESOURCE_ADDRESS_LENGTH]) -> ResourceAddress {
+    if bytes[0] != ENTITY_TYPE_RESOURCE {
+        panic!("Not a resource address, wrong entity type");
+    }
+    let mut inner = [0u8; RESOURCE_ADDRESS_LENGTH - 1];
+    let mut i = 1;
+    while i < RESOURCE_ADDRESS_LENGTH {
+        inner[i - 1] = bytes[i];
+        i += 1;
+    }
+    ResourceAddress(inner)
+}
+
+fn resource_address_bytes(address: &ResourceAddress) -> [u8; RESOURCE_ADDRESS_LENGTH] {
+    let mut bytes = [ENTITY_TYPE_RESOURCE; RESOURCE_ADDRESS_LENGTH];
+    bytes[1..].copy_from_slice(&address.0);
+    bytes
+}
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32M_CONST: u32 = 0x2bc830a3;
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+const fn polymod_step(checksum: u32, value: u8) -> u32 {
+    let top = checksum >> 25;
+    let mut checksum = ((checksum & 0x1ffffff) << 5) ^ (value as u32);
+    let mut i = 0;
+    while i < 5 {
+        if (top >> i) & 1 == 1 {
+            checksum ^= GENERATOR[i];
+        }
+        i += 1;
+    }
+    checksum
+}
+
+const fn charset_value(c: u8) -> u8 {
+    let mut i = 0;
+    while i < CHARSET.len() {
+        if CHARSET[i] == c {
+            return i as u8;
+        }
+        i += 1;
+    }
+    panic!("Invalid bech32 character in resource address");
+}
+
+const fn starts_with(s: &[u8], prefix: &[u8]) -> bool {
+    if s.len() < prefix.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if s[i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Returns the index of the `1` separating the human readable part from the data
+pub(crate) const fn separator_index(s: &[u8]) -> usize {
+    let mut i = s.len();
+    while i > 0 {
+        i -= 1;
+        if s[i] == b'1' {
+            return i;
+        }
+    }
+    panic!("Missing separator in resource address");
+}
+
+/// Whether the human readable part of `address` is exactly `hrp`
+pub const fn has_hrp(address: &str, hrp: &str) -> bool {
+    let s = address.as_bytes();
+    let sep = separator_index(s);
+    sep == hrp.len() && starts_with(s, hrp.as_bytes())
+}
+
+const fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+const fn is_hex(s: &[u8]) -> bool {
+    let mut i = 0;
+    while i < s.len() {
+        if hex_value(s[i]).is_none() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// the hex string form of Scrypto v0.4.1, as written by ResourceAddress's Display
+const fn decode_hex(s: &[u8]) -> ResourceAddress {
+    if s.len() != RESOURCE_ADDRESS_LENGTH * 2 {
+        panic!("Invalid resource address length");
+    }
+    let mut bytes = [0u8; RESOURCE_ADDRESS_LENGTH];
+    let mut i = 0;
+    while i < RESOURCE_ADDRESS_LENGTH {
+        match (hex_value(s[2 * i]), hex_value(s[2 * i + 1])) {
+            (Some(high), Some(low)) => bytes[i] = (high << 4) | low,
+            _ => panic!("Invalid hex character in resource address"),
+        }
+        i += 1;
+    }
+    resource_address_from_bytes(&bytes)
+}
+
+// the bech32m form, for any network (the caller checks the human readable part)
+const fn decode_bech32m(s: &[u8]) -> ResourceAddress {
+    let sep = separator_index(s);
+    if !starts_with(s, RESOURCE_HRP_PREFIX.as_bytes()) || sep == RESOURCE_HRP_PREFIX.len() {
+        panic!("Not a resource address, expected human readable part resource_<network>");
+    }
+    if s.len() < sep + 1 + 6 {
+        panic!("Resource address too short");
+    }
+
+    // checksum over the expanded human readable part and all the data (including the checksum itself)
+    let mut checksum = 1;
+    let mut i = 0;
+    while i < sep {
+        if s[i] < 33 || s[i] > 126 || (s[i] >= b'A' && s[i] <= b'Z') {
+            panic!("Invalid character in resource address human readable part");
+        }
+        checksum = polymod_step(checksum, s[i] >> 5);
+        i += 1;
+    }
+    checksum = polymod_step(checksum, 0);
+    i = 0;
+    while i < sep {
+        checksum = polymod_step(checksum, s[i] & 31);
+        i += 1;
+    }
+    i = sep + 1;
+    while i < s.len() {
+        checksum = polymod_step(checksum, charset_value(s[i]));
+        i += 1;
+    }
+    if checksum != BECH32M_CONST {
+        panic!("Invalid resource address checksum");
+    }
+
+    // convert the 5 bit groups (without the checksum) to bytes
+    let mut bytes = [0u8; RESOURCE_ADDRESS_LENGTH];
+    let mut length = 0;
+    let mut accumulator: u32 = 0;
+    let mut bits: u32 = 0;
+    i = sep + 1;
+    while i < s.len() - 6 {
+        accumulator = ((accumulator << 5) | charset_value(s[i]) as u32) & 0x1fff; // never more than 12 bits pending
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            if length == RESOURCE_ADDRESS_LENGTH {
+                panic!("Resource address too long");
+            }
+            bytes[length] = ((accumulator >> bits) & 0xff) as u8;
+            length += 1;
+        }
+        i += 1;
+    }
+    if bits >= 5 || (accumulator & ((1u32 << bits) - 1)) != 0 {
+        panic!("Invalid padding in resource address");
+    }
+    if length != RESOURCE_ADDRESS_LENGTH {
+        panic!("Resource address too short");
+    }
+    resource_address_from_bytes(&bytes)
+}
+
+/// Parses a resource address, in hex or bech32m for the network selected at build time
+///
+/// ```
+/// # use scrypto::prelude::*;
+/// # use scrypto_statictypes::prelude::*;
+/// const ADDRESS: ResourceAddress = parse_resource_address("030000000000000000000000000000000000000000000000000004");
+/// assert_eq!(ADDRESS, RADIX_TOKEN);
+///
+/// const SIM: ResourceAddress = parse_resource_address("resource_sim1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydqrwhcyc");
+/// assert_eq!(SIM, parse_resource_address("030102030405060708090a0b0c0d0e0f101112131415161718191a"));
+/// ```
+///
+/// An invalid checksum is a compile error:
+/// ```compile_fail,E0080
+/// # use scrypto::prelude::*;
+/// # use scrypto_statictypes::prelude::*;
+/// const SIM: ResourceAddress = parse_resource_address("resource_sim1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydqrwhcyd");
+/// ```
+///
+/// and so is an address of another network (here mainnet, with the default network `local`):
+/// ```compile_fail,E0080
+/// # use scrypto::prelude::*;
+/// # use scrypto_statictypes::prelude::*;
+/// const RDX: ResourceAddress = parse_resource_address("resource_rdx1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydq9vjp08");
+/// ```
+///
+/// # Panics
+/// Panics if the address is neither, the checksum is invalid, the data is not a resource address, or the human readable
+/// part is not the one of `NETWORK`.
+pub const fn parse_resource_address(address: &str) -> ResourceAddress {
+    parse_network_resource_address(NETWORK, address)
+}
+
+/// Network a per-network address in `declare_resource!` belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Network {
+    Local,
+    Testnet,
+    Mainnet,
+}
+
+/// The network selected at build time
+///
+/// The `SCRYPTO_STATICTYPES_NETWORK` environment variable (`local`, `testnet` or `mainnet`) takes precedence over the
+/// `network_testnet` and `network_mainnet` features, and the default is `local` (the simulator).
+pub const NETWORK: Network = network_from(option_env!("SCRYPTO_STATICTYPES_NETWORK"));
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && starts_with(a.as_bytes(), b.as_bytes())
+}
+
+const fn network_from(name: Option<&str>) -> Network {
+    match name {
+        Some(name) if str_eq(name, "local") => Network::Local,
+        Some(name) if str_eq(name, "testnet") => Network::Testnet,
+        Some(name) if str_eq(name, "mainnet") => Network::Mainnet,
+        Some(_) => panic!("Invalid SCRYPTO_STATICTYPES_NETWORK, expected local, testnet or mainnet"),
+        None if cfg!(feature = "network_mainnet") => Network::Mainnet,
+        None if cfg!(feature = "network_testnet") => Network::Testnet,
+        None => Network::Local,
+    }
+}
+
+// human readable part (or its prefix for testnets, which are numbered) of resource addresses on each network
+const fn network_hrp(network: Network) -> &'static str {
+    match network {
+        Network::Local => "resource_sim",
+        Network::Testnet => "resource_tdx",
+        Network::Mainnet => "resource_rdx",
+    }
+}
+
+/// Parses a resource address, in hex or bech32m which must belong to `network`
+///
+/// # Panics
+/// Panics if the address is neither, the checksum is invalid, the data is not a resource address, or the human readable
+/// part is not the one of `network`.
+pub const fn parse_network_resource_address(network: Network, address: &str) -> ResourceAddress {
+    let s = address.as_bytes();
+    if s.len() == RESOURCE_ADDRESS_LENGTH * 2 && is_hex(s) {
+        return decode_hex(s); // the hex form has no network
+    }
+    let hrp = network_hrp(network);
+    let matches = match network {
+        Network::Testnet => starts_with(s, hrp.as_bytes()),
+        _ => has_hrp(address, hrp),
+    };
+    if !matches {
+        panic!("Resource address is not for the declared network");
+    }
+    decode_bech32m(s)
+}
+
+/// Selects the address for the network selected at build time, `None` (bound at runtime) if there is none
+pub const fn select_network_address(table: &[(Network, ResourceAddress)]) -> Option<ResourceAddress> {
+    let mut i = 0;
+    while i < table.len() {
+        if table[i].0 as u8 == NETWORK as u8 {
+            return Some(table[i].1);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Encodes a resource address as bech32m for `network`, the inverse of `parse_network_resource_address`
+pub(crate) fn encode_resource_address(network: Network, address: &ResourceAddress) -> String {
+    let hrp = network_hrp(network).as_bytes();
+
+    // convert the entity type byte and address to 5 bit groups, padded with zeros
+    let mut data = Vec::with_capacity((RESOURCE_ADDRESS_LENGTH * 8 + 4) / 5 + 6);
+    let mut accumulator: u32 = 0;
+    let mut bits: u32 = 0;
+    for byte in resource_address_bytes(address) {
+        accumulator = ((accumulator << 8) | byte as u32) & 0x1fff;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            data.push(((accumulator >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        data.push(((accumulator << (5 - bits)) & 31) as u8);
+    }
+
+    let mut checksum = 1;
+    for c in hrp {
+        checksum = polymod_step(checksum, c >> 5);
+    }
+    checksum = polymod_step(checksum, 0);
+    for c in hrp {
+        checksum = polymod_step(checksum, c & 31);
+    }
+    for value in data.iter().copied().chain([0; 6]) {
+        checksum = polymod_step(checksum, value);
+    }
+    checksum ^= BECH32M_CONST;
+    data.extend((0..6).map(|i| ((checksum >> (5 * (5 - i))) & 31) as u8));
+
+    let mut encoded = String::with_capacity(hrp.len() + 1 + data.len());
+    encoded.push_str(network_hrp(network));
+    encoded.push('1');
+    encoded.extend(data.into_iter().map(|value| CHARSET[value as usize] as char));
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrypto::prelude::{ECDSA_TOKEN, RADIX_TOKEN, SYSTEM_TOKEN};
+    use std::str::FromStr;
+
+    // 03 01 02 .. 1a, encoded with the reference bech32m implementation of BIP 350
+    const BYTES: &str = "030102030405060708090a0b0c0d0e0f101112131415161718191a";
+    const SIM: &str = "resource_sim1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydqrwhcyc";
+    const RDX: &str = "resource_rdx1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydq9vjp08";
+    const TDX: &str = "resource_tdx_b_1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydqxtjm0a";
+
+    fn sequential() -> ResourceAddress {
+        let mut inner = [0u8; RESOURCE_ADDRESS_LENGTH - 1];
+        for (i, byte) in inner.iter_mut().enumerate() {
+            *byte = i as u8 + 1;
+        }
+        ResourceAddress(inner)
+    }
+
+    #[test]
+    fn test_radix_token_string_form() {
+        const ADDRESS: ResourceAddress =
+            parse_resource_address("030000000000000000000000000000000000000000000000000004");
+        assert_eq!(ADDRESS, RADIX_TOKEN);
+        assert_eq!(parse_resource_address(&RADIX_TOKEN.to_string()), RADIX_TOKEN);
+        assert_eq!(
+            parse_resource_address(&RADIX_TOKEN.to_string().to_uppercase()),
+            RADIX_TOKEN
+        );
+    }
+
+    // the string form of Scrypto v0.4.1 is checked against its own parser, ResourceAddress::from_str
+    #[test]
+    fn test_scrypto_string_form() {
+        let vectors = [
+            RADIX_TOKEN.to_string(),
+            ECDSA_TOKEN.to_string(),
+            SYSTEM_TOKEN.to_string(),
+            "030000000000000000000000000000000000000000000000000004".to_owned(),
+            BYTES.to_owned(),
+            BYTES.to_uppercase(),
+            "03ffffffffffffffffffffffffffffffffffffffffffffffffffff".to_owned(),
+        ];
+        for vector in vectors {
+            let expected = ResourceAddress::from_str(&vector).unwrap();
+            assert_eq!(parse_resource_address(&vector), expected, "{}", vector);
+            for network in [Network::Local, Network::Testnet, Network::Mainnet] {
+                assert_eq!(parse_network_resource_address(network, &vector), expected, "{}", vector);
+            }
+        }
+        assert_eq!(parse_resource_address(&ECDSA_TOKEN.to_string()), ECDSA_TOKEN);
+        assert_eq!(parse_resource_address(&SYSTEM_TOKEN.to_string()), SYSTEM_TOKEN);
+
+        // rejected by both
+        for vector in [
+            "020000000000000000000000000000000000000000000000000004", // wrong entity type
+            "0300000000000000000000000000000000000000000000000004",   // too short
+        ] {
+            assert!(ResourceAddress::from_str(vector).is_err(), "{}", vector);
+            assert!(std::panic::catch_unwind(|| parse_resource_address(vector)).is_err(), "{}", vector);
+        }
+    }
+
+    #[test]
+    fn test_known_vectors() {
+        assert_eq!(parse_resource_address(BYTES), sequential());
+        assert_eq!(sequential().to_string(), BYTES);
+        assert_eq!(parse_network_resource_address(Network::Local, SIM), sequential());
+        assert_eq!(parse_network_resource_address(Network::Mainnet, RDX), sequential());
+        assert_eq!(parse_network_resource_address(Network::Testnet, TDX), sequential());
+        assert_eq!(encode_resource_address(Network::Local, &sequential()), SIM);
+        assert_eq!(encode_resource_address(Network::Mainnet, &sequential()), RDX);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let addresses = [
+            RADIX_TOKEN,
+            sequential(),
+            ResourceAddress([0; RESOURCE_ADDRESS_LENGTH - 1]),
+            ResourceAddress([0xff; RESOURCE_ADDRESS_LENGTH - 1]),
+        ];
+        for address in addresses {
+            assert_eq!(parse_resource_address(&address.to_string()), address);
+            for network in [Network::Local, Network::Testnet, Network::Mainnet] {
+                let encoded = encode_resource_address(network, &address);
+                assert_eq!(
+                    parse_network_resource_address(network, &encoded),
+                    address,
+                    "{}",
+                    encoded
+                );
+            }
+            let encoded = encode_resource_address(NETWORK, &address);
+            assert_eq!(parse_resource_address(&encoded), address, "{}", encoded);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Resource address is not for the declared network")]
+    fn test_wrong_network() {
+        parse_network_resource_address(Network::Local, RDX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid resource address checksum")]
+    fn test_bad_checksum() {
+        parse_network_resource_address(Network::Local, &SIM.replace("hcyc", "hcyd"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a resource address, wrong entity type")]
+    fn test_wrong_entity_type() {
+        parse_resource_address("020000000000000000000000000000000000000000000000000004");
+    }
+}
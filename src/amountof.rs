@@ -0,0 +1,368 @@
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use scrypto::prelude::*;
+
+use crate::internal::*;
+
+/// An amount of a declared resource
+///
+/// Returned by `amount()` on the typed containers and accepted by their `take`, `mint` and `create_proof_by_amount`
+/// methods, so an amount of one resource cannot silently be used with another.  Amounts of the same resource can be
+/// added and subtracted, and any amount can be scaled by a plain number.  Cross-resource math must be explicit, with
+/// `.convert::<OTHER>()` or `.unwrap()` to get back the `Decimal`.
+pub struct AmountOf<RES> {
+    pub(crate) inner: Decimal,
+    pub(crate) phantom: PhantomData<RES>,
+}
+
+impl<RES: Resource> AmountOf<RES> {
+    /// Creates an amount of this resource.
+    #[inline(always)]
+    pub fn new<A: Into<Decimal>>(amount: A) -> Self {
+        Self {
+            inner: amount.into(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a zero amount of this resource.
+    #[inline(always)]
+    pub fn zero() -> Self {
+        Self::new(Decimal::zero())
+    }
+
+    /// Whether this amount is zero.
+    #[inline(always)]
+    pub fn is_zero(&self) -> bool {
+        self.inner.is_zero()
+    }
+
+    /// Whether this amount is positive.
+    #[inline(always)]
+    pub fn is_positive(&self) -> bool {
+        self.inner.is_positive()
+    }
+
+    /// Whether this amount is negative.
+    #[inline(always)]
+    pub fn is_negative(&self) -> bool {
+        self.inner.is_negative()
+    }
+
+    /// Rounds this amount to the given divisibility (number of decimal places).
+    ///
+    /// # Panics
+    /// Panics if the divisibility is greater than `DIVISIBILITY_MAXIMUM`.
+    #[inline(always)]
+    pub fn round(self, divisibility: u8, mode: Rounding) -> Self {
+        AmountOf::<RES>::new(round_decimal(self.inner, divisibility, mode))
+    }
+
+    /// Explicitly reinterprets this amount 1:1 as an amount of another resource.
+    #[inline(always)]
+    pub fn convert<OTHER: Resource>(self) -> AmountOf<OTHER> {
+        AmountOf::<OTHER>::new(self.inner)
+    }
+}
+
+/// How to round an amount which has more decimal places than a resource's divisibility allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    TowardsZero,
+    AwayFromZero,
+    TowardsNegativeInfinity,
+    TowardsPositiveInfinity,
+    TowardsNearestAndHalfAwayFromZero,
+}
+
+pub(crate) fn round_decimal(value: Decimal, divisibility: u8, mode: Rounding) -> Decimal {
+    assert!(divisibility <= DIVISIBILITY_MAXIMUM, "Invalid divisibility {}", divisibility);
+    let unit = 10i128.pow((DIVISIBILITY_MAXIMUM - divisibility) as u32);
+    let (quotient, remainder) = (value.0 / unit, value.0 % unit); // truncates towards zero
+    if remainder == 0 {
+        return value;
+    }
+    let away = if value.0 > 0 { quotient + 1 } else { quotient - 1 };
+    let rounded = match mode {
+        Rounding::TowardsZero => quotient,
+        Rounding::AwayFromZero => away,
+        Rounding::TowardsNegativeInfinity => if value.0 > 0 { quotient } else { away },
+        Rounding::TowardsPositiveInfinity => if value.0 > 0 { away } else { quotient },
+        Rounding::TowardsNearestAndHalfAwayFromZero => if remainder.abs() * 2 >= unit { away } else { quotient },
+    };
+    Decimal(rounded * unit)
+}
+
+impl<RES: Resource> Unwrap for AmountOf<RES> {
+    type Value = Decimal;
+
+    #[inline(always)]
+    fn unwrap(self) -> Self::Value {
+        self.inner
+    }
+}
+
+impl<RES: Resource> UncheckedInto<RES, AmountOf<RES>> for Decimal {
+    #[inline(always)]
+    fn unchecked_into(self) -> AmountOf<RES> {
+        AmountOf::<RES>::new(self)
+    }
+}
+
+/// Conversion into an `AmountOf<RES>`, accepting the same `AmountOf<RES>`, a `Decimal`, a plain number or a decimal string
+///
+/// An amount of another resource is not accepted, it must be converted explicitly with `.convert::<RES>()`.
+///
+/// ```
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// fn take_some(bucket: &mut BucketOf<XRD>, amount: AmountOf<XRD>) -> BucketOf<XRD> {
+///     let mut taken = bucket.take(amount);
+///     taken.put(bucket.take("1.5"));
+///     taken
+/// }
+/// ```
+///
+/// ```compile_fail,E0277
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(USDC);
+///
+/// fn take_some(bucket: &mut BucketOf<XRD>, amount: AmountOf<USDC>) -> BucketOf<XRD> {
+///     bucket.take(amount)
+/// }
+/// ```
+pub trait IntoAmountOf<RES: Resource> {
+    fn into_amount_of(self) -> AmountOf<RES>;
+}
+
+impl<RES: Resource> IntoAmountOf<RES> for AmountOf<RES> {
+    #[inline(always)]
+    fn into_amount_of(self) -> AmountOf<RES> {
+        self
+    }
+}
+
+// implemented for concrete types (instead of a blanket over Into<Decimal>) so an AmountOf is never accepted as a plain
+// number and the impls for AmountOf<RES> do not overlap
+macro_rules! impl_from_decimal_like {
+    ( $($t:ty),* ) => {
+        $(
+            impl<RES: Resource> IntoAmountOf<RES> for $t {
+                #[inline(always)]
+                fn into_amount_of(self) -> AmountOf<RES> {
+                    AmountOf::<RES>::new(self)
+                }
+            }
+
+            impl<RES: Resource> From<$t> for AmountOf<RES> {
+                #[inline(always)]
+                fn from(amount: $t) -> Self {
+                    AmountOf::<RES>::new(amount)
+                }
+            }
+
+            impl<RES: Resource> Mul<$t> for AmountOf<RES> {
+                type Output = AmountOf<RES>;
+
+                #[inline(always)]
+                fn mul(self, rhs: $t) -> Self::Output {
+                    AmountOf::<RES>::new(self.inner * Decimal::from(rhs))
+                }
+            }
+
+            impl<RES: Resource> Div<$t> for AmountOf<RES> {
+                type Output = AmountOf<RES>;
+
+                #[inline(always)]
+                fn div(self, rhs: $t) -> Self::Output {
+                    AmountOf::<RES>::new(self.inner / Decimal::from(rhs))
+                }
+            }
+
+            impl<RES: Resource> MulAssign<$t> for AmountOf<RES> {
+                #[inline(always)]
+                fn mul_assign(&mut self, rhs: $t) {
+                    self.inner = self.inner * Decimal::from(rhs);
+                }
+            }
+
+            impl<RES: Resource> DivAssign<$t> for AmountOf<RES> {
+                #[inline(always)]
+                fn div_assign(&mut self, rhs: $t) {
+                    self.inner = self.inner / Decimal::from(rhs);
+                }
+            }
+        )*
+    };
+}
+
+impl_from_decimal_like!(Decimal, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+// decimal strings are accepted as amounts like by the untyped API, but are not numbers to scale an amount by
+macro_rules! impl_from_decimal_str {
+    ( $($t:ty),* ) => {
+        $(
+            impl<RES: Resource> IntoAmountOf<RES> for $t {
+                #[inline(always)]
+                fn into_amount_of(self) -> AmountOf<RES> {
+                    AmountOf::<RES>::new(self)
+                }
+            }
+
+            impl<RES: Resource> From<$t> for AmountOf<RES> {
+                #[inline(always)]
+                fn from(amount: $t) -> Self {
+                    AmountOf::<RES>::new(amount)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_decimal_str!(&str, String);
+
+// arithmetic only between amounts of the same resource
+
+impl<RES: Resource> Add for AmountOf<RES> {
+    type Output = AmountOf<RES>;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self::Output {
+        AmountOf::<RES>::new(self.inner + rhs.inner)
+    }
+}
+
+impl<RES: Resource> Sub for AmountOf<RES> {
+    type Output = AmountOf<RES>;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        AmountOf::<RES>::new(self.inner - rhs.inner)
+    }
+}
+
+impl<RES: Resource> AddAssign for AmountOf<RES> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        self.inner = self.inner + rhs.inner;
+    }
+}
+
+impl<RES: Resource> SubAssign for AmountOf<RES> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.inner = self.inner - rhs.inner;
+    }
+}
+
+impl<RES: Resource> Neg for AmountOf<RES> {
+    type Output = AmountOf<RES>;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        AmountOf::<RES>::new(-self.inner)
+    }
+}
+
+// manual impls instead of derive, since derive would require the same traits on the (uninhabited) RES
+
+impl<RES: Resource> Clone for AmountOf<RES> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<RES: Resource> Copy for AmountOf<RES> {}
+
+impl<RES: Resource> Default for AmountOf<RES> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<RES: Resource> PartialEq for AmountOf<RES> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<RES: Resource> Eq for AmountOf<RES> {}
+
+impl<RES: Resource> PartialOrd for AmountOf<RES> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<RES: Resource> Ord for AmountOf<RES> {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
+impl<RES: Resource> std::hash::Hash for AmountOf<RES> {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.hash(state)
+    }
+}
+
+impl<RES: Resource> std::fmt::Debug for AmountOf<RES> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {}", self.inner, std::any::type_name::<RES>())
+    }
+}
+
+impl<RES: Resource> std::fmt::Display for AmountOf<RES> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+//=====
+// SBOR
+//=====
+
+// AmountOf looks exactly like a Decimal, there is nothing to check when decoding
+
+impl<RES: Resource> sbor::TypeId for AmountOf<RES> {
+    #[inline(always)]
+    fn type_id() -> u8 {
+        <Decimal as sbor::TypeId>::type_id()
+    }
+}
+
+impl<RES: Resource> sbor::Describe for AmountOf<RES> {
+    #[inline(always)]
+    fn describe() -> sbor::describe::Type {
+        <Decimal as sbor::Describe>::describe()
+    }
+}
+
+impl<RES: Resource> sbor::Encode for AmountOf<RES> {
+    #[inline(always)]
+    fn encode_value(&self, encoder: &mut sbor::Encoder) {
+        <Decimal as sbor::Encode>::encode_value(&self.inner, encoder)
+    }
+}
+
+impl<RES: Resource> sbor::Decode for AmountOf<RES> {
+    #[inline(always)]
+    fn decode_value(decoder: &mut sbor::Decoder) -> Result<Self, sbor::DecodeError> {
+        <Decimal as sbor::Decode>::decode_value(decoder).map(AmountOf::<RES>::new)
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeSet;
+
+use scrypto::prelude::{AuthZone, NonFungibleId, Proof};
+
+use crate::amountof::*;
+use crate::exts::address_of;
+use crate::internal::*;
+use crate::proofof::*;
+use crate::registry::ResourceBinding;
+
+/// Typed operations on the auth zone of the current call frame
+///
+/// Like `AuthZone` but with `ProofOf<RES>`, ie. `AuthZoneOf::pop_of::<BADGE>()` or
+/// `AuthZoneOf::create_proof_of::<BADGE>()`.  Every proof taken from the auth zone goes through the checked conversion
+/// from `Proof`, the same as a `ProofOf` argument, so without `runtime_typechecks` this is only available for resources
+/// declared with an address.
+///
+/// A `ProofOf` returned here is dropped like any other, so it can't dangle if it is neither pushed back nor returned.
+#[derive(Debug)]
+pub struct AuthZoneOf {}
+
+impl AuthZoneOf {
+    /// Pushes a proof to the auth zone.
+    #[inline(always)]
+    pub fn push<RES: Resource>(proof: ProofOf<RES>) {
+        // unwrap takes the Proof out, so the ProofOf Drop doesn't drop what's now in the auth zone
+        AuthZone::push(proof.unwrap())
+    }
+
+    /// Pops the most recently pushed proof from the auth zone.
+    ///
+    /// # Panics
+    /// Panics if the auth zone is empty, or the proof is not of `RES` or is empty.
+    #[inline(always)]
+    pub fn pop_of<RES: Resource>() -> ProofOf<RES>
+    where Proof: TryIntoOf<RES, ProofOf<RES>> {
+        Self::try_pop_of().unwrap_or_else(|e| panic!("AuthZoneOf: {}", e))
+    }
+
+    /// Pops the most recently pushed proof from the auth zone, or gives it back on a mismatch.
+    ///
+    /// The returned `Proof` is no longer in the auth zone, use `AuthZone::push` to put it back.
+    ///
+    /// # Panics
+    /// Panics if the auth zone is empty.
+    #[inline(always)]
+    pub fn try_pop_of<RES: Resource>() -> Result<ProofOf<RES>, ConversionError<Proof>>
+    where Proof: TryIntoOf<RES, ProofOf<RES>> {
+        AuthZone::pop().try_into_of()
+    }
+
+    /// Creates a proof of all the resource in the auth zone.
+    ///
+    /// # Panics
+    /// Panics if the resource is not declared with, or bound to, an address, or the proof is empty.
+    #[inline(always)]
+    pub fn create_proof_of<RES: ResourceBinding>() -> ProofOf<RES>
+    where Proof: TryIntoOf<RES, ProofOf<RES>> {
+        Self::checked(AuthZone::create_proof(address_of::<RES>("AuthZoneOf")))
+    }
+
+    /// Creates a proof of an amount of the resource in the auth zone.
+    ///
+    /// # Panics
+    /// Panics if the resource is not declared with, or bound to, an address, or there is not enough in the auth zone.
+    #[inline(always)]
+    pub fn create_proof_by_amount_of<RES: ResourceBinding, A: IntoAmountOf<RES>>(amount: A) -> ProofOf<RES>
+    where Proof: TryIntoOf<RES, ProofOf<RES>> {
+        let address = address_of::<RES>("AuthZoneOf");
+        Self::checked(AuthZone::create_proof_by_amount(amount.into_amount_of().unwrap(), address))
+    }
+
+    /// Creates a proof of some non-fungibles of the resource in the auth zone.
+    ///
+    /// # Panics
+    /// Panics if the resource is not declared with, or bound to, an address, or the non-fungibles are not in the auth
+    /// zone.
+    #[inline(always)]
+    pub fn create_proof_by_ids_of<RES: NonFungibleResource + ResourceBinding>(
+        ids: &BTreeSet<NonFungibleId>,
+    ) -> ProofOf<RES>
+    where
+        Proof: TryIntoOf<RES, ProofOf<RES>>,
+    {
+        Self::checked(AuthZone::create_proof_by_ids(ids, address_of::<RES>("AuthZoneOf")))
+    }
+
+    // the proof is created for RES's address so a mismatch is unexpected, but still checked like any other Proof
+    #[inline(always)]
+    fn checked<RES: Resource>(proof: Proof) -> ProofOf<RES>
+    where Proof: TryIntoOf<RES, ProofOf<RES>> {
+        proof.try_into_of().unwrap_or_else(|e| panic!("AuthZoneOf: {}", e))
+    }
+}
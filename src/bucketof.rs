@@ -1,5 +1,6 @@
 use scrypto::prelude::*;
 
+use crate::amountof::*;
 use crate::proofof::*;
 use crate::internal::*;
 use crate::resourceof::ResourceOf;
@@ -7,7 +8,8 @@ use crate::resourceof::ResourceOf;
 #[cfg(feature = "runtime_typechecks")]
 use crate::runtime::runtimechecks;
 
-impl_wrapper_struct!(BucketOf<RES>, Bucket);
+impl_wrapper_struct!(BucketOf<RES>, Bucket, noderef); // no deref, so the methods of the wrong kind can't be reached
+impl_wrapper_inner!(BucketOf<RES>, Bucket);
 impl_SBOR_traits!(BucketOf<RES>, Bucket);
 impl SBORable for Bucket {}
 impl Container for Bucket {}
@@ -40,14 +42,68 @@ impl<RES: Resource> BucketOf<RES> {
         self.inner.put(other.inner)
     }
 
+    /// Returns the amount of resources in this bucket.
+    #[inline(always)]
+    pub fn amount(&self) -> AmountOf<RES> {
+        self.inner.amount().unchecked_into()
+    }
+
     /// Takes some amount of resources from this bucket.
     #[inline(always)]
-    pub fn take<A: Into<Decimal>>(&mut self, amount: A) -> Self {
-        self.inner.take(amount).unchecked_into()
+    pub fn take<A: IntoAmountOf<RES>>(&mut self, amount: A) -> Self {
+        self.inner.take(amount.into_amount_of().unwrap()).unchecked_into()
+    }
+
+    /// Burns resource within this bucket.
+    #[inline(always)]
+    pub fn burn(self) {
+        // must define this instead of leaning on Deref because of self not &self (needs DerefMove which doesn't exist yet)
+        self.inner.burn();
+    }
+
+    /// Creates an ownership proof of this bucket.
+    #[inline(always)]
+    pub fn create_proof(&self) -> ProofOf<RES> {
+        // self.inner.create_proof().unchecked_into()
+        UncheckedIntoProofOf::unchecked_into(self.inner.create_proof())
+    }
+
+    /// Returns the resource definition of resources in this bucket.
+    #[inline(always)]
+    pub fn resource_manager(&self) -> ResourceOf<RES> {
+        self.inner.resource_address().unchecked_into()
+    }
+
+    /// Returns the resource address.
+    #[inline(always)]
+    pub fn resource_address(&self) -> ResourceAddress {
+        self.inner.resource_address()
     }
 
+    /// Checks if this bucket is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<RES: NonFungibleResource> BucketOf<RES> {
     /// Takes a non-fungible from this bucket, by key.
     ///
+    /// Only for non-fungible (or undeclared kind) resources, for a `fungible` one it doesn't compile:
+    /// ```compile_fail,E0599
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// declare_resource!(MYTOKEN, fungible);
+    ///
+    /// fn take(bucket: &mut BucketOf<MYTOKEN>, id: &NonFungibleId) -> BucketOf<MYTOKEN> {
+    ///     bucket.take_non_fungible(id)
+    /// }
+    /// ```
+    ///
     /// # Panics
     /// Panics if this is not a non-fungible bucket or the specified non-fungible resource is not found.
     #[inline(always)]
@@ -57,6 +113,20 @@ impl<RES: Resource> BucketOf<RES> {
 
     /// Takes non-fungibles from this bucket.
     ///
+    /// Only for non-fungible (or undeclared kind) resources, for a `fungible` one it doesn't compile:
+    /// ```compile_fail,E0599
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// declare_resource!(MYTOKEN, fungible);
+    ///
+    /// fn take(bucket: &mut BucketOf<MYTOKEN>, ids: &BTreeSet<NonFungibleId>) -> BucketOf<MYTOKEN> {
+    ///     bucket.take_non_fungibles(ids)
+    /// }
+    /// ```
+    ///
     /// # Panics
     /// Panics if this is not a non-fungible bucket or the specified non-fungible resource is not found.
     #[inline(always)]
@@ -64,39 +134,116 @@ impl<RES: Resource> BucketOf<RES> {
         self.inner.take_non_fungibles(non_fungible_ids).unchecked_into()
     }
 
-    /// Burns resource within this bucket.
+    /// Returns the ids of all non-fungibles in this bucket.
+    ///
+    /// Only for non-fungible (or undeclared kind) resources, for a `fungible` one it doesn't compile:
+    /// ```compile_fail,E0599
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// declare_resource!(MYTOKEN, fungible);
+    ///
+    /// fn ids(bucket: &BucketOf<MYTOKEN>) -> BTreeSet<NonFungibleId> {
+    ///     bucket.non_fungible_ids()
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible bucket.
     #[inline(always)]
-    pub fn burn(self) {
-        // must define this instead of leaning on Deref because of self not &self (needs DerefMove which doesn't exist yet)
-        self.inner.burn();
+    pub fn non_fungible_ids(&self) -> BTreeSet<NonFungibleId> {
+        self.inner.non_fungible_ids()
     }
 
-    /// Creates an ownership proof of this bucket.
+    /// Returns all the non-fungible units contained.
+    ///
+    /// When the data type is declared with `declare_resource!(NAME, non_fungible = Data)` any other `T` will not compile.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible bucket.
     #[inline(always)]
-    pub fn create_proof(&self) -> ProofOf<RES> {
-        // self.inner.create_proof().unchecked_into()
-        UncheckedIntoProofOf::unchecked_into(self.inner.create_proof())
+    pub fn non_fungibles<T: NonFungibleData>(&self) -> Vec<NonFungible<T>>
+    where RES::Kind: AcceptsNonFungibleData<T> {
+        self.inner.non_fungibles()
     }
 
-    /// Returns the resource definition of resources in this bucket.
+    /// Returns a singleton non-fungible.
+    ///
+    /// When the data type is declared with `declare_resource!(NAME, non_fungible = Data)` any other `T` will not compile.
+    ///
+    /// # Panics
+    /// Panics if this is not a singleton bucket.
     #[inline(always)]
-    pub fn resource_manager(&self) -> ResourceOf<RES> {
-        self.inner.resource_address().unchecked_into()
+    pub fn non_fungible<T: NonFungibleData>(&self) -> NonFungible<T>
+    where RES::Kind: AcceptsNonFungibleData<T> {
+        self.inner.non_fungible()
+    }
+}
+
+impl<RES: NonFungibleDataResource> BucketOf<RES> {
+    /// Returns the data of all the non-fungibles in this bucket, using the declared data type.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible bucket.
+    #[inline(always)]
+    pub fn non_fungible_data(&self) -> BTreeMap<NonFungibleId, RES::Data> {
+        self.inner
+            .non_fungibles::<RES::Data>()
+            .into_iter()
+            .map(|non_fungible| (non_fungible.id(), non_fungible.data()))
+            .collect()
+    }
+
+    /// Updates the data of a non-fungible, using the declared data type.
+    ///
+    /// # Panics
+    /// Panics if the non-fungible does not exist or the update is not authorized.
+    #[inline(always)]
+    pub fn update_non_fungible_data(&self, non_fungible_id: &NonFungibleId, data: RES::Data) {
+        self.resource_manager().update_non_fungible_data(non_fungible_id, data)
     }
 }
 
 impl_TryFrom_Slice!(BucketOf<RES>, ParseBucketError);
 
+#[cfg(feature = "runtime_typechecks")]
+impl<RES: runtimechecks::Resource> TryIntoOf<RES, BucketOf<RES>> for Bucket {
+    fn try_into_of(self) -> Result<BucketOf<RES>, ConversionError<Self>> {
+        match runtimechecks::check_address::<RES>(self.resource_address()) {
+            Ok(()) => Ok(self.unchecked_into()),
+            Err(error) => Err(ConversionError { value: self, error }),
+        }
+    }
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: ResourceDecl> TryIntoOf<RES, BucketOf<RES>> for Bucket {
+    #[inline(always)]
+    fn try_into_of(self) -> Result<BucketOf<RES>, ConversionError<Self>> {
+        match check_declared_address::<RES>(self.resource_address()) {
+            Ok(()) => Ok(self.unchecked_into()),
+            Err(error) => Err(ConversionError { value: self, error }),
+        }
+    }
+}
+
 #[cfg(feature = "runtime_typechecks")]
 impl<RES: runtimechecks::Resource> From<Bucket> for BucketOf<RES> {
     fn from(bucket: Bucket) -> Self {
-        if !runtimechecks::check_address::<RES>(bucket.resource_address()) {
-            // let tmp_bucket =
-            //     ResourceBuilder::new_fungible(DIVISIBILITY_MAXIMUM).initial_supply_fungible(1);
-            // bucket.put(tmp_bucket); // this will trigger resource def mismatch error: Err(InvokeError(Trap(Trap { kind: Host(BucketError(MismatchingResourceManager)) })))
-                                    // shouldn't get here, but just in case (and to help the compiler)
-            panic!("BucketOf mismatch");
-        }
-        bucket.unchecked_into()
+        // let tmp_bucket =
+        //     ResourceBuilder::new_fungible(DIVISIBILITY_MAXIMUM).initial_supply_fungible(1);
+        // bucket.put(tmp_bucket); // this will trigger resource def mismatch error: Err(InvokeError(Trap(Trap { kind: Host(BucketError(MismatchingResourceManager)) })))
+        //                         // shouldn't get here, but just in case (and to help the compiler)
+        bucket.try_into_of().unwrap_or_else(|e| panic!("BucketOf mismatch: {}", e))
+    }
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: ResourceDecl> From<Bucket> for BucketOf<RES> {
+    #[inline(always)]
+    fn from(bucket: Bucket) -> Self {
+        bucket.try_into_of().unwrap_or_else(|e| panic!("BucketOf mismatch: {}", e))
     }
 }
@@ -0,0 +1,61 @@
+use scrypto::prelude::ComponentAddress;
+
+use crate::exts::call_method_of;
+use crate::internal::*;
+
+#[cfg(feature = "runtime_typechecks")]
+use crate::runtime::runtimechecks;
+
+impl_wrapper_struct!(ComponentOf<RES: Blueprint>, ComponentAddress);
+impl_SBOR_traits!(ComponentOf<RES>, ComponentAddress, Blueprint, BlueprintDecl);
+impl SBORable for ComponentAddress {}
+impl Container for ComponentAddress {}
+
+impl<RES: Blueprint> ComponentOf<RES> {
+    /// Calls a method of this component, decoding the return as `T` (see `call_method_of`).
+    #[inline(always)]
+    pub fn call<T: sbor::Decode>(&self, method: &str, args: Vec<Vec<u8>>) -> T {
+        call_method_of(self.inner, method, args)
+    }
+}
+
+#[cfg(feature = "runtime_typechecks")]
+impl<RES: BlueprintDecl> TryIntoOf<RES, ComponentOf<RES>> for ComponentAddress {
+    fn try_into_of(self) -> Result<ComponentOf<RES>, ConversionError<Self>> {
+        match runtimechecks::check_blueprint::<RES>(self) {
+            Ok(()) => Ok(self.unchecked_into()),
+            Err(error) => Err(ConversionError { value: self, error }),
+        }
+    }
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: BlueprintDecl> TryIntoOf<RES, ComponentOf<RES>> for ComponentAddress {
+    #[inline(always)]
+    fn try_into_of(self) -> Result<ComponentOf<RES>, ConversionError<Self>> {
+        Ok(self.unchecked_into())
+    }
+}
+
+// the same with and without runtime_typechecks, .try_into_of() does any checks
+impl<RES: BlueprintDecl> From<ComponentAddress> for ComponentOf<RES> {
+    fn from(component_address: ComponentAddress) -> Self {
+        component_address.try_into_of().unwrap_or_else(|e| panic!("ComponentOf mismatch: {}", e))
+    }
+}
+
+// Implement == and != between ComponentAddress and ComponentOf
+
+impl<RES: Blueprint> PartialEq<ComponentOf<RES>> for ComponentAddress {
+    #[inline(always)]
+    fn eq(&self, other: &ComponentOf<RES>) -> bool {
+        self == &other.inner
+    }
+}
+
+impl<RES: Blueprint> PartialEq<ComponentAddress> for ComponentOf<RES> {
+    #[inline(always)]
+    fn eq(&self, other: &ComponentAddress) -> bool {
+        &self.inner == other
+    }
+}
@@ -0,0 +1,144 @@
+use scrypto::prelude::{ComponentAddress, ResourceAddress};
+
+/// Why a value could not be converted to a typed wrapper, ie. `Bucket` to `BucketOf<MYTOKEN>`
+///
+/// `resource` is the type name of the declared resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaticTypeError {
+    /// The address is not the one declared for (or already bound to) the resource
+    Mismatch {
+        resource: &'static str,
+        expected: ResourceAddress,
+        actual: ResourceAddress,
+    },
+    /// The address is already bound to another resource
+    AddressAlreadyBound {
+        resource: &'static str,
+        address: ResourceAddress,
+        bound_to: &'static str,
+    },
+    /// The resource is not bound yet, and with `trusted_bindings` the address is not from a trusted source which may bind it
+    Unbound {
+        resource: &'static str,
+        address: ResourceAddress,
+    },
+    /// A `ProofOf` can not be created from an empty proof
+    EmptyProof { resource: &'static str },
+    /// The resource on ledger is not of the declared kind
+    KindMismatch {
+        resource: &'static str,
+        address: ResourceAddress,
+        expected: &'static str,
+        actual: &'static str,
+    },
+    /// The resource on ledger does not have the declared metadata (or divisibility)
+    DeclarationMismatch {
+        resource: &'static str,
+        address: ResourceAddress,
+        field: &'static str,
+    },
+    /// The conversion can not be checked without `runtime_typechecks`, and is not safe to do unchecked
+    Unchecked { resource: &'static str },
+    /// The component is not of the declared blueprint (`field` is the package or blueprint name)
+    BlueprintMismatch {
+        blueprint: &'static str,
+        component: ComponentAddress,
+        field: &'static str,
+    },
+    /// The resource is not declared with, or bound to, an address, which is needed (ie. to call an account)
+    NoAddress { resource: &'static str },
+    /// The resource could not be bound because the runtime registry is full
+    RegistryFull { resource: &'static str },
+}
+
+impl std::fmt::Display for StaticTypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StaticTypeError::Mismatch { resource, expected, actual } => {
+                write!(f, "{} mismatch: expected {} but got {}", resource, expected, actual)
+            }
+            StaticTypeError::AddressAlreadyBound { resource, address, bound_to } => {
+                write!(f, "{} cannot bind {}: already bound to {}", resource, address, bound_to)
+            }
+            StaticTypeError::Unbound { resource, address } => write!(
+                f,
+                "{} is not bound, and {} is not from a trusted source (component state, ResourceBuilderOf or bind)",
+                resource, address
+            ),
+            StaticTypeError::EmptyProof { resource } => write!(f, "Will not create empty ProofOf<{}>", resource),
+            StaticTypeError::KindMismatch { resource, address, expected, actual } => {
+                write!(f, "{} kind mismatch: {} is {} but declared {}", resource, address, actual, expected)
+            }
+            StaticTypeError::DeclarationMismatch { resource, address, field } => {
+                write!(f, "{} declaration mismatch: {} has a different {}", resource, address, field)
+            }
+            StaticTypeError::Unchecked { resource } => write!(
+                f,
+                "Unsafe creation of ProofOf<{}> from Proof.  Declare the resource with an address, enable scrypto_statictypes/runtime_typechecks or use .unchecked_into()",
+                resource
+            ),
+            StaticTypeError::BlueprintMismatch { blueprint, component, field } => {
+                write!(f, "{} blueprint mismatch: {} has a different {}", blueprint, component, field)
+            }
+            StaticTypeError::NoAddress { resource } => write!(f, "{} has no declared or bound address", resource),
+            StaticTypeError::RegistryFull { resource } => {
+                write!(f, "{} cannot be bound: the runtime registry is full", resource)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StaticTypeError {}
+
+/// What happens when a `runtime_typechecks` check fails
+///
+/// The default is `Strict`, or `Warn`/`Count` with the `policy_warn`/`policy_count` features, and may be overridden per
+/// resource with `declare_resource!(BADGE, policy = strict)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailurePolicy {
+    /// Log the failure with `error!` and continue with the unchecked conversion
+    Warn,
+    /// Like `Warn`, and also record the failure for a later assertion with `registry::mismatches()`
+    Count,
+    /// Fail the conversion (panic with `.into()`, a `DecodeError` when decoding, or an `Err` from `try_into_of()`)
+    Strict,
+}
+
+/// The failure policy of resources without an override
+pub const DEFAULT_FAILURE_POLICY: FailurePolicy = if cfg!(feature = "policy_warn") {
+    FailurePolicy::Warn
+} else if cfg!(feature = "policy_count") {
+    FailurePolicy::Count
+} else {
+    FailurePolicy::Strict
+};
+
+/// A failed `try_into_of()`, which gives back the value so it can still be used (ie. to return a wrong bucket)
+#[derive(Debug)]
+pub struct ConversionError<T> {
+    pub value: T,
+    pub error: StaticTypeError,
+}
+
+impl<T> ConversionError<T> {
+    /// Returns the value which could not be converted.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> From<ConversionError<T>> for StaticTypeError {
+    #[inline(always)]
+    fn from(e: ConversionError<T>) -> Self {
+        e.error
+    }
+}
+
+impl<T> std::fmt::Display for ConversionError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for ConversionError<T> {}
@@ -1,26 +1,141 @@
+use std::collections::BTreeSet;
+
+use crate::amountof::*;
 use crate::bucketof::*;
 /// DepositOf and WithdrawOf
 use crate::internal::*;
+use crate::proofof::*;
+use crate::registry::ResourceBinding;
 use crate::resourceof::*;
 use scrypto::prelude::{
-    Runtime, scrypto_decode, scrypto_encode, Bucket, Decimal, ComponentAddress
+    Runtime, scrypto_decode, scrypto_encode, Bucket, Decimal, ComponentAddress, NonFungibleId, PackageAddress
 };
 
 #[cfg(feature = "runtime_typechecks")]
 use crate::runtime::runtimechecks;
 
+// Typed calls
+//
+
+/// Calls a method of another component, decoding the return as `T`
+///
+/// The arguments are encoded as usual, ie. `args![bucket_of, proof_of]` since the typed containers encode as the
+/// containers they wrap.  A typed return value like `BucketOf<MYTOKEN>` is decoded with the same checks as
+/// `From<Bucket>`, so with `runtime_typechecks` a component handing back the wrong resource is caught at the boundary.
+/// Without it only a resource declared with an address is compared, any other is accepted unchecked.
+///
+/// # Panics
+/// Panics if the return value can not be decoded as `T`, including a failed check.
+pub fn call_method_of<T: sbor::Decode>(component_address: ComponentAddress, method: &str, args: Vec<Vec<u8>>) -> T {
+    let rtn = Runtime::call_method(component_address, method, args);
+    scrypto_decode(&rtn).unwrap_or_else(|e| panic!("call_method_of {}: unexpected return value: {:?}", method, e))
+}
+
+/// Calls a function of a blueprint, decoding the return as `T` (see `call_method_of`)
+///
+/// # Panics
+/// Panics if the return value can not be decoded as `T`, including a failed check.
+pub fn call_function_of<T: sbor::Decode>(
+    package_address: PackageAddress,
+    blueprint_name: &str,
+    function: &str,
+    args: Vec<Vec<u8>>,
+) -> T {
+    let rtn = Runtime::call_function(package_address, blueprint_name, function, args);
+    scrypto_decode(&rtn).unwrap_or_else(|e| {
+        panic!("call_function_of {}::{}: unexpected return value: {:?}", blueprint_name, function, e)
+    })
+}
+
 /// marker to force equivalence between 2 resources (type parameters) at compile time
 pub trait ResourceIs<RES: Resource> {}
 impl<RES: Resource> ResourceIs<RES> for RES {}
 
 /// Proxy for an Account taking the place of the removed scrypto::core::Account API
-struct Account {
+///
+/// Calls the methods of the account component with typed containers, ie. `account.deposit_of::<MYTOKEN>(bucket)` or
+/// `account.balance_of::<MYTOKEN>()`.  Every bucket or proof returned by the account is checked (decoded as the typed
+/// container, or converted with `try_into_of()`), since an external component could return any resource.  Withdrawing
+/// and creating proofs needs the account's authorization, as with any other call to the account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Account {
     component: ComponentAddress,
 }
 impl Account {
-    fn address(&self) -> ComponentAddress {
+    /// Creates a proxy for the account component at this address.
+    #[inline(always)]
+    pub fn new(component: ComponentAddress) -> Self {
+        Self { component }
+    }
+
+    /// Returns the address of the account component.
+    #[inline(always)]
+    pub fn address(&self) -> ComponentAddress {
         self.component
     }
+
+    /// Deposits buckets of a resource into the account.
+    #[inline(always)]
+    pub fn deposit_batch_of<RES: Resource>(&self, buckets: Vec<BucketOf<RES>>) {
+        let buckets: Vec<Bucket> = buckets.into_iter().map(Unwrap::unwrap).collect();
+        call_method_of(self.address(), "deposit_batch", vec![scrypto_encode(&buckets)])
+    }
+
+    /// Returns the balance of a resource in the account.
+    ///
+    /// # Panics
+    /// Panics if the resource is not declared with, or bound to, an address.
+    #[inline(always)]
+    pub fn balance_of<RES: ResourceBinding>(&self) -> AmountOf<RES> {
+        let args = vec![scrypto_encode(&address_of::<RES>("Account"))];
+        let balance: Decimal = call_method_of(self.address(), "balance", args);
+        AmountOf::new(balance)
+    }
+
+    /// Withdraws non-fungibles of a resource from the account.
+    ///
+    /// # Panics
+    /// Panics if the resource is not declared with, or bound to, an address.
+    #[inline(always)]
+    pub fn withdraw_non_fungibles_of<RES: NonFungibleResource + ResourceBinding>(
+        &self,
+        ids: &BTreeSet<NonFungibleId>,
+    ) -> BucketOf<RES>
+    where
+        BucketOf<RES>: sbor::Decode,
+    {
+        let args = vec![scrypto_encode(ids), scrypto_encode(&address_of::<RES>("Account"))];
+        call_method_of(self.address(), "withdraw_by_ids", args) // checked since external method call could return any type of bucket
+    }
+
+    /// Creates a proof of a resource in the account.
+    ///
+    /// Without `runtime_typechecks` this is only available for resources declared with an address, as `ProofOf` arguments.
+    ///
+    /// # Panics
+    /// Panics if the resource is not declared with, or bound to, an address, or the proof is empty.
+    #[inline(always)]
+    pub fn create_proof_of<RES: ResourceBinding>(&self) -> ProofOf<RES>
+    where ProofOf<RES>: sbor::Decode {
+        call_method_of(self.address(), "create_proof", vec![scrypto_encode(&address_of::<RES>("Account"))])
+    }
+}
+
+impl From<ComponentAddress> for Account {
+    #[inline(always)]
+    fn from(component: ComponentAddress) -> Self {
+        Self::new(component)
+    }
+}
+
+// the account (and auth zone) methods take a ResourceAddress, so the resource must have a known one
+pub(crate) fn address_of<RES: ResourceBinding>(caller: &str) -> ResourceAddress {
+    RES::bound_address().unwrap_or_else(|| {
+        let error = StaticTypeError::NoAddress {
+            resource: std::any::type_name::<RES>(),
+        };
+        panic!("{}: {}", caller, error)
+    })
 }
 
 // Deposit
@@ -35,9 +150,7 @@ impl Deposit for Account {
         // Account::deposit(self, bucket)
         // Account API removed in Scrypto v0.3.0, use this
         // dynamic implementation instead
-        let args = vec![scrypto_encode(&bucket)];
-        let rtn = Runtime::call_method(self.address(), "deposit", args);
-        scrypto_decode(&rtn).unwrap()
+        call_method_of(self.address(), "deposit", vec![scrypto_encode(&bucket)])
     }
 }
 
@@ -77,8 +190,7 @@ impl Withdraw for Account {
             scrypto_encode(&amount),
             scrypto_encode(&resource_address.into()),
         ];
-        let rtn = Runtime::call_method(self.address(), "withdraw", args);
-        scrypto_decode(&rtn).unwrap()
+        call_method_of(self.address(), "withdraw_by_amount", args)
     }
 }
 
@@ -90,10 +202,12 @@ where Self: Withdraw
     // RHS allows for specifying the resource with the function, or eliding it with the correct ResourceOf
     fn withdraw_of<RHS: runtimechecks::Resource>(
         &self,
-        amount: Decimal,
+        amount: AmountOf<RHS>,
         resource_of: ResourceOf<RHS>,
     ) -> BucketOf<RHS> {
-        <Self as Withdraw>::withdraw(self, amount, resource_of).into() // do checked into here since external method call could return any type of bucket
+        let bucket = <Self as Withdraw>::withdraw(self, amount.unwrap(), resource_of);
+        // checked since external method call could return any type of bucket
+        bucket.try_into_of().unwrap_or_else(|e| panic!("withdraw_of: {}", e))
     }
 
     #[cfg(not(feature = "runtime_typechecks"))]
@@ -101,46 +215,58 @@ where Self: Withdraw
     // RHS allows for specifying the resource with the function, or eliding it with the correct ResourceOf
     fn withdraw_of<RHS: ResourceDecl>(
         &self,
-        amount: Decimal,
+        amount: AmountOf<RHS>,
         resource_of: ResourceOf<RHS>,
     ) -> BucketOf<RHS> {
-        <Self as Withdraw>::withdraw(self, amount, resource_of).into() // do checked into here since external method call could return any type of bucket
+        let bucket = <Self as Withdraw>::withdraw(self, amount.unwrap(), resource_of);
+        // checked since external method call could return any type of bucket
+        bucket.try_into_of().unwrap_or_else(|e| panic!("withdraw_of: {}", e))
     }
 }
 
 #[cfg(not(feature = "runtime_typechecks"))]
 /// Explicitly requires withdraw_of::<RES> syntax instead of of automatically allowing any ResourceOf<_> parameter
+///
+/// The amount is an `AmountOf<RES>`, ie. `account.withdraw_of::<XRD>(10.into(), xrd)`, since a generic amount can't be
+/// combined with the explicit `::<RES>` on our MSRV.
 pub trait WithdrawOfExplicit<RES: ResourceDecl>
 where Self: Withdraw
 {
     #[inline(always)]
     fn withdraw_of<RHS: ResourceDecl>(
         &self,
-        amount: Decimal,
+        amount: AmountOf<RES>,
         resource_of: ResourceOf<RES>,
     ) -> BucketOf<RES>
     where
         RHS: ResourceIs<RES>,
     {
-        <Self as Withdraw>::withdraw(self, amount, resource_of).into() // do checked into here since external method call could return any type of bucket
+        let bucket = <Self as Withdraw>::withdraw(self, amount.unwrap(), resource_of);
+        // checked since external method call could return any type of bucket
+        bucket.try_into_of().unwrap_or_else(|e| panic!("withdraw_of: {}", e))
     }
 }
 
 #[cfg(feature = "runtime_typechecks")]
 /// Explicitly requires withdraw_of::<RES> syntax instead of of automatically allowing any ResourceOf<_> parameter
+///
+/// The amount is an `AmountOf<RES>`, ie. `account.withdraw_of::<XRD>(10.into(), xrd)`, since a generic amount can't be
+/// combined with the explicit `::<RES>` on our MSRV.
 pub trait WithdrawOfExplicit<RES: runtimechecks::Resource>
 where Self: Withdraw
 {
     #[inline(always)]
     fn withdraw_of<RHS: runtimechecks::Resource>(
         &self,
-        amount: Decimal,
+        amount: AmountOf<RES>,
         resource_of: ResourceOf<RES>,
     ) -> BucketOf<RES>
     where
         RHS: ResourceIs<RES>,
     {
-        <Self as Withdraw>::withdraw(self, amount, resource_of).into() // do checked into here since external method call could return any type of bucket
+        let bucket = <Self as Withdraw>::withdraw(self, amount.unwrap(), resource_of);
+        // checked since external method call could return any type of bucket
+        bucket.try_into_of().unwrap_or_else(|e| panic!("withdraw_of: {}", e))
     }
 }
 
@@ -1,11 +1,142 @@
 use std::ops::Deref;
 
-pub use scrypto::prelude::{ResourceAddress, ResourceManager};
+pub use scrypto::prelude::{PackageAddress, ResourceAddress, ResourceManager};
+use scrypto::prelude::NonFungibleData;
+
+use crate::address::Network;
+pub use crate::error::{ConversionError, FailurePolicy, StaticTypeError, DEFAULT_FAILURE_POLICY};
 
 pub trait Resource: std::fmt::Debug {} // supertrait to ensure the correct traits propate to all of the templates
 
 pub trait ResourceDecl: Resource {
     const ADDRESS: Option<ResourceAddress>;
+    // all the per-network addresses, ADDRESS is the one of the network selected at build time
+    const ADDRESSES: &'static [(Network, ResourceAddress)] = &[];
+    type Kind: ResourceKind;
+    // AddressSelected<true> when ADDRESS is Some, which makes it an AddressedResource
+    type Addressed;
+    // declared metadata, applied by ResourceBuilderOf and verified with runtime_typechecks
+    const NAME: Option<&'static str> = None;
+    const SYMBOL: Option<&'static str> = None;
+    const DIVISIBILITY: Option<u8> = None;
+    const METADATA: &'static [(&'static str, &'static str)] = &[];
+    // overrides DEFAULT_FAILURE_POLICY for runtime_typechecks
+    const FAILURE_POLICY: Option<FailurePolicy> = None;
+}
+
+pub trait Blueprint: std::fmt::Debug {} // like Resource, for ComponentOf
+
+pub trait BlueprintDecl: Blueprint {
+    // declared package and blueprint name, verified with runtime_typechecks
+    const NAME: Option<&'static str> = None;
+    const PACKAGE: Option<PackageAddress> = None;
+}
+
+/// Resources declared with an address, ie. `declare_resource!(XRD, RADIX_TOKEN)` (or per-network addresses, including
+/// one for the network selected at build time)
+///
+/// Their `ProofOf` can be checked against `ADDRESS` without `runtime_typechecks`, so it may be a method argument.
+pub trait AddressedResource: ResourceDecl {}
+impl<RES: ResourceDecl> AddressedResource for RES where RES::Addressed: HasAddress {}
+
+/// Whether a declared resource has an address, the `Addressed` type of a declaration
+#[derive(Debug)]
+pub struct AddressSelected<const SELECTED: bool>;
+
+/// Only `AddressSelected<true>`
+pub trait HasAddress {}
+impl HasAddress for AddressSelected<true> {}
+
+#[inline(always)]
+pub const fn address_selected(address: Option<ResourceAddress>) -> bool {
+    address.is_some()
+}
+
+//==============
+// Resource kinds
+//==============
+
+/// Marker for the kind of a declared resource, set with `declare_resource!(MYTOKEN, fungible)` or `declare_resource!(BADGE, non_fungible)`
+pub trait ResourceKind {
+    // how the kind is written in declare_resource! and resource manifests
+    const NAME: Option<&'static str>;
+    fn data_type_name() -> Option<&'static str> {
+        None
+    }
+}
+
+/// Kind for resources declared with `fungible`
+#[derive(Debug)]
+pub enum FungibleKind {}
+impl ResourceKind for FungibleKind {
+    const NAME: Option<&'static str> = Some("fungible");
+}
+
+/// Kind for resources declared with `non_fungible`
+#[derive(Debug)]
+pub enum NonFungibleKind {}
+impl ResourceKind for NonFungibleKind {
+    const NAME: Option<&'static str> = Some("non_fungible");
+}
+
+/// Kind for non-fungible resources declared with a data type `non_fungible = MemberData`
+#[derive(Debug)]
+pub struct NonFungibleKindOf<D>(std::marker::PhantomData<D>);
+impl<D> ResourceKind for NonFungibleKindOf<D> {
+    const NAME: Option<&'static str> = Some("non_fungible");
+    fn data_type_name() -> Option<&'static str> {
+        Some(std::any::type_name::<D>())
+    }
+}
+
+/// Kind for resources declared without a kind, both fungible and non-fungible methods are allowed (checked only by the Radix Engine)
+#[derive(Debug)]
+pub enum UnknownKind {}
+impl ResourceKind for UnknownKind {
+    const NAME: Option<&'static str> = None;
+}
+
+/// Kinds which allow the fungible-only methods (ie. `ResourceOf::mint`)
+pub trait AllowsFungible: ResourceKind {}
+impl AllowsFungible for FungibleKind {}
+impl AllowsFungible for UnknownKind {}
+
+/// Kinds which allow the non-fungible-only methods (ie. `BucketOf::take_non_fungible`)
+pub trait AllowsNonFungible: ResourceKind {}
+impl AllowsNonFungible for NonFungibleKind {}
+impl AllowsNonFungible for UnknownKind {}
+impl<D> AllowsNonFungible for NonFungibleKindOf<D> {}
+
+/// Kinds which accept (or produce) non-fungible data of type `T`, any `T` unless the data type was declared
+pub trait AcceptsNonFungibleData<T: NonFungibleData>: AllowsNonFungible {}
+impl<T: NonFungibleData> AcceptsNonFungibleData<T> for NonFungibleKind {}
+impl<T: NonFungibleData> AcceptsNonFungibleData<T> for UnknownKind {}
+impl<D: NonFungibleData> AcceptsNonFungibleData<D> for NonFungibleKindOf<D> {}
+
+/// Kinds with a declared non-fungible data type
+pub trait HasNonFungibleData: AllowsNonFungible {
+    type Data: NonFungibleData;
+}
+impl<D: NonFungibleData> HasNonFungibleData for NonFungibleKindOf<D> {
+    type Data = D;
+}
+
+/// Declared resources which may be fungible
+pub trait FungibleResource: ResourceDecl {}
+impl<RES: ResourceDecl> FungibleResource for RES where RES::Kind: AllowsFungible {}
+
+/// Declared resources which may be non-fungible
+pub trait NonFungibleResource: ResourceDecl {}
+impl<RES: ResourceDecl> NonFungibleResource for RES where RES::Kind: AllowsNonFungible {}
+
+/// Declared non-fungible resources with a declared data type, ie. `declare_resource!(MEMBER, non_fungible = MemberData)`
+pub trait NonFungibleDataResource: NonFungibleResource {
+    type Data: NonFungibleData;
+}
+impl<RES: ResourceDecl> NonFungibleDataResource for RES
+where RES::Kind: HasNonFungibleData
+{
+    type Data = <RES::Kind as HasNonFungibleData>::Data;
 }
 
 pub trait Container: SBORable {}
@@ -89,6 +220,18 @@ macro_rules! impl_SBOR_traits {
         impl_SBOR_Encode!($w, $t);
         impl_SBOR_Decode!($w, $t);
     };
+    // decoded with $from, see impl_SBOR_Decode!
+    ( $w:ty, $t:ident, $from:ident($source:ident) ) => {
+        impl_SBOR_traits_without_Encode_Decode!($w, $t);
+        impl_SBOR_Encode!($w, $t);
+        impl_SBOR_Decode!($w, $t, $from($source));
+    };
+    // wrappers of other markers than resources (ie. ComponentOf<B: Blueprint>), decoded with .try_into_of() for $decl
+    ( $w:ty, $t:ident, $bound:path, $decl:path ) => {
+        impl_SBOR_traits_without_Encode_Decode!($w, $t, $bound);
+        impl_SBOR_Encode!($w, $t, $bound);
+        impl_SBOR_Decode!($w, $t, $decl);
+    };
 }
 pub(crate) use impl_SBOR_traits; // export for use within crate
 
@@ -96,11 +239,14 @@ pub(crate) use impl_SBOR_traits; // export for use within crate
 // requires $w: WithInner<$t> and $t: From<$w>
 macro_rules! impl_SBOR_traits_without_Encode_Decode {
     ( $w:ty, $t:ident ) => {
+        impl_SBOR_traits_without_Encode_Decode!($w, $t, Resource);
+    };
+    ( $w:ty, $t:ident, $bound:path ) => {
         // use std::ops::{Deref};
         // use sbor::describe::Type;
         // use sbor::{Decode, DecodeError, Decoder, TypeId};
         // use sbor::{Describe, Encode, Encoder};
-        impl<RES: Resource> sbor::TypeId for $w {
+        impl<RES: $bound> sbor::TypeId for $w {
             // TypeId
             #[inline(always)]
             fn type_id() -> u8 {
@@ -109,7 +255,7 @@ macro_rules! impl_SBOR_traits_without_Encode_Decode {
             }
         }
 
-        impl<RES: Resource> sbor::Describe for $w {
+        impl<RES: $bound> sbor::Describe for $w {
             // Describe
             #[inline(always)]
             fn describe() -> sbor::describe::Type {
@@ -125,10 +271,34 @@ pub(crate) use impl_SBOR_traits_without_Encode_Decode; // export for use within
 // Main Wrapper implementation
 //==============
 
-pub trait UncheckedInto<RES: Resource, W> {
+pub trait UncheckedInto<RES, W> {
     fn unchecked_into(self) -> W;
 }
 
+/// Checked conversion into a typed wrapper, like `.into()` but returning the value and why it could not be converted
+/// instead of panicking
+///
+/// Without `runtime_typechecks` only a declared address is checked (a cheap constant comparison), and `Proof` also fails
+/// for a resource without a declared address (it is never converted unchecked).
+/// This is not `TryFrom` since the wrappers already implement `From`, which comes with an infallible `TryFrom`.
+pub trait TryIntoOf<RES, W>: Sized {
+    fn try_into_of(self) -> Result<W, ConversionError<Self>>;
+}
+
+// the only check without runtime_typechecks, a resource without a declared address is converted unchecked
+#[cfg(not(feature = "runtime_typechecks"))]
+#[inline(always)]
+pub(crate) fn check_declared_address<RES: ResourceDecl>(actual: ResourceAddress) -> Result<(), StaticTypeError> {
+    match RES::ADDRESS {
+        Some(expected) if expected != actual => Err(StaticTypeError::Mismatch {
+            resource: std::any::type_name::<RES>(),
+            expected,
+            actual,
+        }),
+        _ => Ok(()),
+    }
+}
+
 pub trait Unwrap {
     type Value;
     fn unwrap(self) -> Self::Value;
@@ -136,12 +306,19 @@ pub trait Unwrap {
 
 macro_rules! impl_wrapper_struct {
     ( $w:ident<RES>, $t:ty, noderef ) => {
+        impl_wrapper_struct!($w<RES: Resource>, $t, noderef);
+    };
+    ( $w:ident<RES>, $t:ty ) => {
+        impl_wrapper_struct!($w<RES: Resource>, $t);
+    };
+    // wrappers of other markers than resources, ie. ComponentOf<RES: Blueprint>
+    ( $w:ident<RES: $b:path>, $t:ty, noderef ) => {
         #[derive(Debug, PartialEq, Eq, Hash)] // Bucket, Proof, Vault are inconsistent, deriving superset (and Proof doesn't use this macro)
         pub struct $w<RES> {
             pub(crate) inner: $t,
             pub(crate) phantom: std::marker::PhantomData<RES>,
         }
-        impl<RES: Resource> Unwrap for $w<RES> {
+        impl<RES: $b> Unwrap for $w<RES> {
             type Value = $t;
 
             #[inline(always)]
@@ -149,7 +326,7 @@ macro_rules! impl_wrapper_struct {
                 self.inner
             }
         }
-        impl<RES: Resource> UncheckedInto<RES, $w<RES>> for $t {
+        impl<RES: $b> UncheckedInto<RES, $w<RES>> for $t {
             #[inline(always)]
             fn unchecked_into(self) -> $w<RES> {
                 $w::<RES> {
@@ -158,19 +335,13 @@ macro_rules! impl_wrapper_struct {
                 }
             }
         }
-        #[cfg(not(feature = "runtime_typechecks"))]
-        impl<RES: Resource> From<$t> for $w<RES> {
-            #[inline(always)]
-            fn from(inner: $t) -> Self {
-                inner.unchecked_into()
-            }
-        }
+        // From<$t> for $w<RES> is implemented by each wrapper, checked with .try_into_of() (which needs a declaration)
 
-        impl_wrapper_common!($w<RES>, $t);
+        impl_wrapper_common!($w<RES: $b>, $t);
     };
-    ( $w:ident<RES>, $t:ty) => {
-        impl_wrapper_struct!($w<RES>, $t, noderef);
-        impl_wrapper_deref!($w<RES>, $t);
+    ( $w:ident<RES: $b:path>, $t:ty ) => {
+        impl_wrapper_struct!($w<RES: $b>, $t, noderef);
+        impl_wrapper_deref!($w<RES: $b>, $t);
     };
 }
 pub(crate) use impl_wrapper_struct; // export for use within crate
@@ -178,7 +349,10 @@ pub(crate) use impl_wrapper_struct; // export for use within crate
 // seperate out deref
 macro_rules! impl_wrapper_deref {
     ( $w:ident<RES>, $t:ty ) => {
-        impl<RES: Resource> std::ops::Deref for $w<RES> {
+        impl_wrapper_deref!($w<RES: Resource>, $t);
+    };
+    ( $w:ident<RES: $b:path>, $t:ty ) => {
+        impl<RES: $b> std::ops::Deref for $w<RES> {
             type Target = $t;
 
             #[inline(always)]
@@ -187,7 +361,7 @@ macro_rules! impl_wrapper_deref {
             }
         }
 
-        impl<RES: Resource> std::ops::DerefMut for $w<RES> {
+        impl<RES: $b> std::ops::DerefMut for $w<RES> {
             #[inline(always)]
             fn deref_mut(&mut self) -> &mut Self::Target {
                 &mut self.inner
@@ -197,11 +371,43 @@ macro_rules! impl_wrapper_deref {
 }
 pub(crate) use impl_wrapper_deref; // export for use within crate
 
+// instead of deref, for the containers with kind gated methods (Deref would expose the inner methods of the wrong kind)
+// the untyped container is still reachable explicitly with with_inner(), as_ref() and as_mut()
+macro_rules! impl_wrapper_inner {
+    ( $w:ident<RES>, $t:ty ) => {
+        impl<RES: Resource> WithInner<$t> for $w<RES> {
+            type Inner = $t;
+            #[inline(always)]
+            fn with_inner<F: FnOnce(&$t) -> O, O>(&self, f: F) -> O {
+                f(&self.inner)
+            }
+        }
+
+        impl<RES: Resource> AsRef<$t> for $w<RES> {
+            #[inline(always)]
+            fn as_ref(&self) -> &$t {
+                &self.inner
+            }
+        }
+
+        impl<RES: Resource> AsMut<$t> for $w<RES> {
+            #[inline(always)]
+            fn as_mut(&mut self) -> &mut $t {
+                &mut self.inner
+            }
+        }
+    };
+}
+pub(crate) use impl_wrapper_inner; // export for use within crate
+
 // common implementations that only depend on traits of $t or $w
-// and are not expected to need custom implementations (like From<$t> for $w<RES> when not(feature = "runtime_typechecks"))
+// and are not expected to need custom implementations (like the checked From<$t> for $w<RES>)
 macro_rules! impl_wrapper_common {
     ( $w:ident<RES>, $t:ty ) => {
-        impl<RES: Resource> From<$w<RES>> for $t {
+        impl_wrapper_common!($w<RES: Resource>, $t);
+    };
+    ( $w:ident<RES: $b:path>, $t:ty ) => {
+        impl<RES: $b> From<$w<RES>> for $t {
             #[inline(always)]
             fn from(wrapped: $w<RES>) -> Self {
                 wrapped.unwrap()
@@ -214,11 +420,14 @@ pub(crate) use impl_wrapper_common; // export for use within crate
 
 macro_rules! impl_SBOR_Encode {
     ( $w:ty, $t:ident ) => {
+        impl_SBOR_Encode!($w, $t, Resource);
+    };
+    ( $w:ty, $t:ident, $bound:path ) => {
         // use std::ops::{Deref};
         // use sbor::describe::Type;
         // use sbor::{Decode, DecodeError, Decoder, TypeId};
         // use sbor::{Describe, Encode, Encoder};
-        impl<RES: Resource> sbor::Encode for $w
+        impl<RES: $bound> sbor::Encode for $w
         where $w: WithInner<$t>
         {
             // Encode
@@ -232,31 +441,76 @@ macro_rules! impl_SBOR_Encode {
 
 pub(crate) use impl_SBOR_Encode; // export for use within crate
 
+// sbor v0.4.1 has no DecodeError variant carrying a message, so the StaticTypeError (expected resource type and actual
+// address) is logged and the decode fails as invalid custom data of the inner type
+#[inline(always)]
+pub(crate) fn decode_error<T: sbor::TypeId>(e: ConversionError<T>) -> sbor::DecodeError {
+    scrypto::prelude::error!("decode failed: {}", e);
+    sbor::DecodeError::InvalidCustomData(T::type_id())
+}
+
 macro_rules! impl_SBOR_Decode {
     ( $w:ty, $t:ident ) => {
         // use std::ops::{Deref};
         // use sbor::describe::Type;
         // use sbor::{Decode, DecodeError, Decoder, TypeId};
         // use sbor::{Describe, Encode, Encoder};
-        // .into implementation needs a ResourceDecl or a runtimechecks::Resource
+        // .try_into_of implementation needs a ResourceDecl or a runtimechecks::Resource
         #[cfg(not(feature = "runtime_typechecks"))]
         impl<RES: ResourceDecl> sbor::Decode for $w {
             // Decode
             #[inline(always)]
             fn decode_value(decoder: &mut sbor::Decoder) -> Result<Self, sbor::DecodeError> {
-                let r = <$t as sbor::Decode>::decode_value(decoder);
-                r.map(|inner| inner.into()) // the .into() saves duplicate code and ensures optional runtime type checks bind the decoded `T`'s ResourceManager (Address) with this type "RES"
+                let inner = <$t as sbor::Decode>::decode_value(decoder)?;
+                // the .try_into_of() saves duplicate code and ensures optional runtime type checks bind the decoded `T`'s ResourceManager (Address) with this type "RES"
+                TryIntoOf::<RES, $w>::try_into_of(inner).map_err(decode_error)
             }
         }
 
-        // .into implementation needs a ResourceDecl or a runtimechecks::Resource
+        // .try_into_of implementation needs a ResourceDecl or a runtimechecks::Resource
         #[cfg(feature = "runtime_typechecks")]
         impl<RES: runtimechecks::Resource> sbor::Decode for $w {
             // Decode
             #[inline(always)]
             fn decode_value(decoder: &mut sbor::Decoder) -> Result<Self, sbor::DecodeError> {
-                let r = <$t as sbor::Decode>::decode_value(decoder);
-                r.map(|inner| inner.into()) // the .into() saves duplicate code and ensures optional runtime type checks bind the decoded `T`'s ResourceManager (Address) with this type "RES"
+                let inner = <$t as sbor::Decode>::decode_value(decoder)?;
+                // the .try_into_of() saves duplicate code and ensures optional runtime type checks bind the decoded `T`'s ResourceManager (Address) with this type "RES"
+                // a mismatch is a DecodeError (with the expected resource type and actual address) instead of a panic
+                TryIntoOf::<RES, $w>::try_into_of(inner).map_err(decode_error)
+            }
+        }
+    };
+    // with runtime_typechecks decoded with $from, which checks the address as coming from BindingSource::$source
+    // (must come before the $decl:path arm, which would parse $from($source) as a path)
+    ( $w:ty, $t:ident, $from:ident($source:ident) ) => {
+        #[cfg(not(feature = "runtime_typechecks"))]
+        impl<RES: ResourceDecl> sbor::Decode for $w {
+            // Decode
+            #[inline(always)]
+            fn decode_value(decoder: &mut sbor::Decoder) -> Result<Self, sbor::DecodeError> {
+                let inner = <$t as sbor::Decode>::decode_value(decoder)?;
+                TryIntoOf::<RES, $w>::try_into_of(inner).map_err(decode_error)
+            }
+        }
+
+        #[cfg(feature = "runtime_typechecks")]
+        impl<RES: runtimechecks::Resource> sbor::Decode for $w {
+            // Decode
+            #[inline(always)]
+            fn decode_value(decoder: &mut sbor::Decoder) -> Result<Self, sbor::DecodeError> {
+                let inner = <$t as sbor::Decode>::decode_value(decoder)?;
+                $from::<RES>(inner, runtimechecks::BindingSource::$source).map_err(decode_error)
+            }
+        }
+    };
+    // the same with and without runtime_typechecks, the .try_into_of implementation for $decl does any checks
+    ( $w:ty, $t:ident, $decl:path ) => {
+        impl<RES: $decl> sbor::Decode for $w {
+            // Decode
+            #[inline(always)]
+            fn decode_value(decoder: &mut sbor::Decoder) -> Result<Self, sbor::DecodeError> {
+                let inner = <$t as sbor::Decode>::decode_value(decoder)?;
+                TryIntoOf::<RES, $w>::try_into_of(inner).map_err(decode_error)
             }
         }
     };
@@ -276,8 +530,9 @@ macro_rules! impl_TryFrom_Slice {
             }
         }
 
+        // .into() checks the declared address, which needs a ResourceDecl
         #[cfg(not(feature = "runtime_typechecks"))]
-        impl<RES: Resource> TryFrom<&[u8]> for $w {
+        impl<RES: ResourceDecl> TryFrom<&[u8]> for $w {
             type Error = $e;
 
             fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
@@ -9,10 +9,40 @@
 //!   - `Vault` --> `VaultOf<MYTOKEN>`
 //!   - `ResourceAddress` --> `ResourceOf<MYTOKEN>`
 //!   - `Proof` --> `ProofOf<MYTOKEN>`
+//!   - `ComponentAddress` --> `ComponentOf<ORACLE>` with `declare_blueprint!(ORACLE, "PriceOracle")`
+//! - Resource-tagged amounts `AmountOf<MYTOKEN>` so an amount of one resource can't silently be used with another.
+//! - Exchange rates `Price<BASE, QUOTE>` to convert between `AmountOf<BASE>` and `AmountOf<QUOTE>` with explicit rounding.
+//! - Typed resource creation with `ResourceBuilderOf::<MYTOKEN>::new_fungible()` returning a `BucketOf<MYTOKEN>`.
 //! - Conveniently defined `XRD` Resource to use with `VaultOf<XRD>`, and friends.
 //! - Simple macro to declare new resources: `declare_resource!(MYTOKEN)`
+//!   - Optionally with a kind: `declare_resource!(MYTOKEN, fungible)` or `declare_resource!(BADGE, non_fungible)` so
+//!     fungible-only (`mint`) and non-fungible-only (`take_non_fungible`, `non_fungible_ids`, ...) methods are only available
+//!     for the right kind of resource
+//!   - Optionally with a non-fungible data type: `declare_resource!(MEMBER, non_fungible = MemberData)` so the typed
+//!     `non_fungible_data()`, `update_non_fungible_data()` and `mint_non_fungible()` reject any other data type at compile time
+//!   - Optionally with type parameters: `declare_resource!(LP<A, B>)` so the LP token of each pair of resources is a distinct type
+//!   - Optionally with metadata: `declare_resource!(MYTOKEN, fungible, name = "MyToken", symbol = "MYT", divisibility = 18)`
+//!   - Optionally with an address: `declare_resource!(XRD, RADIX_TOKEN)` or `declare_resource!(USDC, "resource_sim1...")` (checked at compile time)
+//!   - Optionally per network: `declare_resource!(USDC, local = "resource_sim1...", mainnet = "resource_rdx1...")`, selected with
+//!     the `network_testnet`/`network_mainnet` features or the `SCRYPTO_STATICTYPES_NETWORK` environment variable at build time
+//! - Typed access rules: `rule!(require_of(&badge))`, `require_amount_of(2, &badge)`, `require_any_of!(&admin, &badge)` and
+//!   `field_of!(Self, auth_def)` in place of `require("auth_def")`, checked by the compiler
+//! - Typed auth zone operations: `AuthZoneOf::push(proof)`, `AuthZoneOf::pop_of::<BADGE>()` and `AuthZoneOf::create_proof_of::<BADGE>()` return checked `ProofOf<BADGE>`
+//! - Typed account calls with `Account::new(address)`: `deposit_of`, `deposit_batch_of`, `withdraw_of`,
+//!   `withdraw_non_fungibles_of`, `balance_of::<MYTOKEN>()` and `create_proof_of::<MYTOKEN>()`
+//! - Typed calls to other components: `call_method_of::<BucketOf<USDC>>(component, "swap", args![bucket])` checks the returned resource
+//!   (with `runtime_typechecks`, or against its declared address)
+//! - Typed stubs for the components a blueprint calls: `typed_interface! { pub trait Exchange { fn swap(&self, input: BucketOf<XRD>) -> BucketOf<USDC>; } pub struct ExchangeComponent; }`
+//! - Intentional aliases share the type: `declare_resource_alias!(WXRD = XRD)` makes `BucketOf<WXRD>` a `BucketOf<XRD>`
+//! - Optional feature `manifest` to share declarations with a `resources.toml` manifest: `include_resources!("resources.toml")`
+//!   and `resource_manifest!(MYTOKEN).save("resources.toml")`
 //! - Optional feature `runtime_typechecks` for safety critical code, or use in
-//!   testing.
+//!   testing.  Use `try_into_of()` to handle a mismatch (`StaticTypeError`) instead of panicking.
+//!   - With `trusted_bindings` a resource declared without an address is only bound from trusted sources: a `VaultOf`
+//!     decoded from component state, `ResourceBuilderOf` or `ResourceOf::bind`, never from a method argument.  The
+//!     exception is a decoded `ResourceOf` of a resource not bound yet, which is accepted without binding (component
+//!     state and arguments can't be told apart), so `bind` a `ResourceOf` argument before trusting it.
+//!   - Bind deliberately with `MYTOKEN::bind(address)` and inspect bindings with `registry::bound_address::<MYTOKEN>()` or `registry::dump()`.
 //!
 //! # Quick Start
 //!
@@ -127,12 +157,29 @@
 //!     }
 //! }
 //! ```
+#[cfg(all(feature = "policy_warn", feature = "policy_count"))]
+compile_error!("features policy_warn and policy_count are mutually exclusive");
+
+#[cfg(all(feature = "network_testnet", feature = "network_mainnet"))]
+compile_error!("features network_testnet and network_mainnet are mutually exclusive");
+
 pub mod prelude;
 
+mod accessrules;
+mod address;
+mod amountof;
+mod authzoneof;
 mod bucketof;
+mod componentof;
 mod proofof;
+mod error;
 mod exts;
 mod internal;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+mod price;
+pub mod registry;
+mod resourcebuilderof;
 mod resourceof;
 mod runtime;
 mod vaultof;
@@ -144,4 +191,4 @@ use scrypto::prelude::RADIX_TOKEN;
 
 use crate::macros::*;
 
-declare_resource!(XRD, RADIX_TOKEN);
+declare_resource!(XRD, RADIX_TOKEN, fungible);
@@ -1,37 +1,550 @@
 pub use crate::internal::{
-    ResourceAddress, Resource as StaticResource, ResourceDecl as StaticResourceDecl,
+    ResourceAddress, Resource as StaticResource, ResourceDecl as StaticResourceDecl, FailurePolicy as StaticFailurePolicy,
+    AddressSelected as StaticAddressSelected, address_selected, Blueprint as StaticBlueprint,
+    BlueprintDecl as StaticBlueprintDecl, PackageAddress,
+    FungibleKind, NonFungibleKind, NonFungibleKindOf, UnknownKind,
+};
+pub use scrypto::prelude::{
+    require_all_of, require_any_of, scrypto_encode, AccessRule, AccessRuleNode, ComponentAddress,
+};
+pub use crate::accessrules::{FieldOf as StaticFieldOf, ResourceRuleOf as StaticResourceRuleOf};
+pub use crate::address::{
+    parse_network_resource_address, parse_resource_address, select_network_address, Network as StaticNetwork,
 };
 
+/// Declares a resource type for use with `BucketOf`, `VaultOf`, `ResourceOf` and `ProofOf`
+///
+/// After the name, any of these options may follow (separated by commas):
+/// - an address expression `declare_resource!(XRD, RADIX_TOKEN)` or an address string, in hex or bech32m for the selected
+///   network `declare_resource!(USDC, "resource_sim1...")`, which is validated at compile time, must be the first option
+/// - or per-network addresses `local = "resource_sim1..."`, `testnet = "..."` and `mainnet = "..."`, selected at build
+///   time with the `SCRYPTO_STATICTYPES_NETWORK` environment variable or the `network_testnet`/`network_mainnet` features
+///   (not both, that is a compile error)
+/// - the kind `fungible`, `non_fungible` or `non_fungible = MemberData` to also declare the `NonFungibleData` type
+/// - `name = "MyToken"`, `symbol = "MYT"` and `divisibility = 18`
+/// - any other metadata `metadata = { "url" => "https://example.com" }`
+/// - the `runtime_typechecks` failure policy `policy = strict`, `policy = warn` or `policy = count`
+///
+/// A resource with an address is an `AddressedResource`, so its `ProofOf` can be checked (and be a method argument)
+/// without `runtime_typechecks`.  With per-network addresses that is only when there is one for the selected network:
+/// ```
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(
+///     USDC,
+///     local = "resource_sim1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydqrwhcyc",
+///     mainnet = "resource_rdx1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydq9vjp08",
+/// );
+///
+/// fn addressed<RES: AddressedResource>() {}
+/// fn check() {
+///     addressed::<USDC>();
+/// }
+/// ```
+///
+/// and without an address for the default network `local`, it is not:
+/// ```compile_fail,E0277
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(USDC, mainnet = "resource_rdx1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydq9vjp08");
+///
+/// fn addressed<RES: AddressedResource>() {}
+/// fn check() {
+///     addressed::<USDC>();
+/// }
+/// ```
+///
+/// An address can't be given together with per-network addresses, for any network:
+/// ```compile_fail
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(XRD_ANYWHERE, RADIX_TOKEN, mainnet = "resource_rdx1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydq9vjp08");
+/// ```
+///
+/// The name may have type parameters, ie. `declare_resource!(LP<A, B>)` for the LP token of any pair of resources, so
+/// `BucketOf<LP<XRD, USDC>>` and `BucketOf<LP<USDC, XRD>>` are different types.  Each parametrization is a distinct
+/// resource (bound to its own address with `runtime_typechecks`).
+///
+/// ```
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(USDC);
+/// declare_resource!(LP<A, B>, fungible);
+///
+/// fn pool(lp: BucketOf<LP<XRD, USDC>>) -> (VaultOf<LP<XRD, USDC>>, ResourceOf<LP<XRD, USDC>>) {
+///     let resource = lp.resource_manager();
+///     (VaultOf::with_bucket(lp), resource)
+/// }
+/// ```
+///
+/// and the parameters can't be mixed up:
+/// ```compile_fail,E0308
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(USDC);
+/// declare_resource!(LP<A, B>, fungible);
+///
+/// fn swap(lp: BucketOf<LP<XRD, USDC>>) -> BucketOf<LP<USDC, XRD>> {
+///     lp
+/// }
+/// ```
+///
+/// ```
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(MYTOKEN, fungible, name = "MyToken", symbol = "MYT", divisibility = 18);
+/// ```
+///
+/// The kind gates the kind specific methods, so the non-fungible methods of a `fungible` resource don't compile:
+/// ```compile_fail,E0599
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(MYTOKEN, fungible);
+///
+/// fn ids(proof: &ProofOf<MYTOKEN>) -> BTreeSet<NonFungibleId> {
+///     proof.non_fungible_ids()
+/// }
+/// ```
+///
+/// and neither does minting a `non_fungible` resource by amount (the kind gated methods of `BucketOf`, `VaultOf` and
+/// `ResourceOf` each have their own example):
+/// ```compile_fail,E0599
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(BADGE, non_fungible);
+///
+/// fn mint(badge: &ResourceOf<BADGE>) {
+///     let _ = badge.mint(1);
+/// }
+/// ```
 #[macro_export]
 macro_rules! declare_resource {
-    ( type $x:ident ) => {
-        impl StaticResource for $x {}
-        impl StaticResourceDecl for $x {
-            const ADDRESS: Option<ResourceAddress> = None;
+    // internal rules
+    ( @impl $x:ident [$($g:ident)*], $addr:expr, $kind:ty, { $($consts:tt)* } ) => {
+        impl<$($g: StaticResource),*> StaticResource for $x<$($g),*> {}
+        impl<$($g: StaticResource),*> StaticResourceDecl for $x<$($g),*> {
+            const ADDRESS: Option<ResourceAddress> = $addr;
+            type Kind = $kind;
+            // a per-network address is only there for some networks, so this is not always true with an address
+            type Addressed = StaticAddressSelected<{ address_selected($addr) }>;
+            $($consts)*
         }
     };
-    ( type $x:ident, $e:expr  ) => {
-        impl StaticResource for $x {}
-        impl StaticResourceDecl for $x {
-            const ADDRESS: Option<ResourceAddress> = Some($e);
-        }
-    };
-    ( $x:ident ) => {
+    ( @enum $x:ident [] ) => {
         #[derive(Debug)]
         #[allow(non_camel_case_types)]
         pub enum $x {}
-        impl StaticResource for $x {}
-        impl StaticResourceDecl for $x {
-            const ADDRESS: Option<ResourceAddress> = None;
+    };
+    // uninhabited like the plain enum, but using every parameter
+    ( @enum $x:ident [$($g:ident)+] ) => {
+        #[allow(non_camel_case_types)]
+        pub struct $x<$($g),+> {
+            _never: ::std::convert::Infallible,
+            _phantom: ::std::marker::PhantomData<($($g,)+)>,
+        }
+        impl<$($g),+> ::std::fmt::Debug for $x<$($g),+> {
+            fn fmt(&self, _: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self._never {}
+            }
         }
     };
-    ( $x:ident, $e:expr  ) => {
+    // option parsing, accumulating [address] [kind] [networks] { consts } while munching the options
+    // the keyword arms must come before the $e:expr arms so `fungible` is not parsed as an expression
+    ( @opts $x:ident [$($g:ident)*] [None] [$($kind:tt)*] [] { $($c:tt)* } $(,)? ) => {
+        $crate::declare_resource!(@impl $x [$($g)*], None, $($kind)*, { $($c)* });
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [] { $($c:tt)* } $(,)? ) => {
+        $crate::declare_resource!(@impl $x [$($g)*], $($addr)*, $($kind)*, { $($c)* });
+    };
+    ( @opts $x:ident [$($g:ident)*] [None] [$($kind:tt)*] [$($net:tt)+] { $($c:tt)* } $(,)? ) => {
+        $crate::declare_resource!(@impl $x [$($g)*], select_network_address(&[$($net)+]), $($kind)*, {
+            $($c)* const ADDRESSES: &'static [(StaticNetwork, ResourceAddress)] = &[$($net)+];
+        });
+    };
+    // the plain address would be ignored in favour of the per-network ones
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)+] [$($kind:tt)*] [$($net:tt)+] { $($c:tt)* } $(,)? ) => {
+        compile_error!(concat!(
+            "declare_resource!(", stringify!($x), "): either an address or per-network addresses (local, testnet, mainnet), not both"
+        ));
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , fungible $($rest:tt)* ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [FungibleKind] [$($net)*] { $($c)* } $($rest)*);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , non_fungible = $d:ty , $($rest:tt)* ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [NonFungibleKindOf<$d>] [$($net)*] { $($c)* } , $($rest)*);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , non_fungible = $d:ty ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [NonFungibleKindOf<$d>] [$($net)*] { $($c)* });
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , non_fungible $($rest:tt)* ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [NonFungibleKind] [$($net)*] { $($c)* } $($rest)*);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , name = $v:expr $(, $($rest:tt)*)? ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* const NAME: Option<&'static str> = Some($v); } $(, $($rest)*)?);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , symbol = $v:expr $(, $($rest:tt)*)? ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* const SYMBOL: Option<&'static str> = Some($v); } $(, $($rest)*)?);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , divisibility = $v:expr $(, $($rest:tt)*)? ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* const DIVISIBILITY: Option<u8> = Some($v); } $(, $($rest)*)?);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , metadata = { $($k:expr => $v:expr),* $(,)? } $($rest:tt)* ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* const METADATA: &'static [(&'static str, &'static str)] = &[$(($k, $v)),*]; } $($rest)*);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , policy = strict $($rest:tt)* ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* const FAILURE_POLICY: Option<StaticFailurePolicy> = Some(StaticFailurePolicy::Strict); } $($rest)*);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , policy = warn $($rest:tt)* ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* const FAILURE_POLICY: Option<StaticFailurePolicy> = Some(StaticFailurePolicy::Warn); } $($rest)*);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , policy = count $($rest:tt)* ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* const FAILURE_POLICY: Option<StaticFailurePolicy> = Some(StaticFailurePolicy::Count); } $($rest)*);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , local = $a:literal $(, $($rest:tt)*)? ) => {
+        $crate::declare_resource!(@network $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* } Local $a $(, $($rest)*)?);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , testnet = $a:literal $(, $($rest:tt)*)? ) => {
+        $crate::declare_resource!(@network $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* } Testnet $a $(, $($rest)*)?);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , mainnet = $a:literal $(, $($rest:tt)*)? ) => {
+        $crate::declare_resource!(@network $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)*] { $($c)* } Mainnet $a $(, $($rest)*)?);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , $a:literal $(, $($rest:tt)*)? ) => {
+        const _: ResourceAddress = parse_resource_address($a); // free consts are always evaluated, so an invalid address is a compile error even if ADDRESS is never used
+        $crate::declare_resource!(@opts $x [$($g)*] [Some(parse_resource_address($a))] [$($kind)*] [$($net)*] { $($c)* } $(, $($rest)*)?);
+    };
+    ( @opts $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } , $e:expr $(, $($rest:tt)*)? ) => {
+        $crate::declare_resource!(@opts $x [$($g)*] [Some($e)] [$($kind)*] [$($net)*] { $($c)* } $(, $($rest)*)?);
+    };
+    // one address of a per-network table, all of them are validated (including the human readable part for the network) even if not selected
+    ( @network $x:ident [$($g:ident)*] [$($addr:tt)*] [$($kind:tt)*] [$($net:tt)*] { $($c:tt)* } $n:ident $a:literal $($rest:tt)* ) => {
+        const _: ResourceAddress = parse_network_resource_address(StaticNetwork::$n, $a);
+        $crate::declare_resource!(@opts $x [$($g)*] [$($addr)*] [$($kind)*] [$($net)* (StaticNetwork::$n, parse_network_resource_address(StaticNetwork::$n, $a)),] { $($c)* } $($rest)*);
+    };
+    // entry points
+    ( type $x:ident < $($g:ident),+ $(,)? > $($opts:tt)* ) => {
+        $crate::declare_resource!(@opts $x [$($g)+] [None] [UnknownKind] [] {} $($opts)*);
+    };
+    ( type $x:ident $($opts:tt)* ) => {
+        $crate::declare_resource!(@opts $x [] [None] [UnknownKind] [] {} $($opts)*);
+    };
+    ( $x:ident < $($g:ident),+ $(,)? > $($opts:tt)* ) => {
+        $crate::declare_resource!(@enum $x [$($g)+]);
+        $crate::declare_resource!(type $x<$($g),+> $($opts)*);
+    };
+    ( $x:ident $($opts:tt)* ) => {
+        $crate::declare_resource!(@enum $x []);
+        $crate::declare_resource!(type $x $($opts)*);
+    };
+}
+
+/// Declares a blueprint type for use with `ComponentOf`
+///
+/// Optionally with the blueprint name `declare_blueprint!(ORACLE, "PriceOracle")` and the package it is published in
+/// `declare_blueprint!(ORACLE, "PriceOracle", package = ORACLE_PACKAGE)`, which are verified with `runtime_typechecks`
+/// whenever a `ComponentAddress` is converted (or decoded) to a `ComponentOf<ORACLE>`.
+///
+/// ```
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_blueprint!(ORACLE, "PriceOracle");
+/// declare_blueprint!(ACCOUNTS, "Account", package = ACCOUNT_PACKAGE);
+/// declare_blueprint!(ANY);
+///
+/// fn price(oracle: &ComponentOf<ORACLE>) -> Decimal {
+///     oracle.call("price", vec![])
+/// }
+/// ```
+///
+/// A `ComponentOf` of one blueprint is not a `ComponentOf` of another:
+/// ```compile_fail,E0308
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_blueprint!(ORACLE, "PriceOracle");
+/// declare_blueprint!(EXCHANGE, "Exchange");
+///
+/// fn oracle(exchange: ComponentOf<EXCHANGE>) -> ComponentOf<ORACLE> {
+///     exchange
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_blueprint {
+    // internal rules
+    ( @impl $x:ident { $($c:tt)* } ) => {
         #[derive(Debug)]
         #[allow(non_camel_case_types)]
         pub enum $x {}
-        impl StaticResource for $x {}
-        impl StaticResourceDecl for $x {
-            const ADDRESS: Option<ResourceAddress> = Some($e);
+        impl StaticBlueprint for $x {}
+        impl StaticBlueprintDecl for $x {
+            $($c)*
+        }
+    };
+    // entry points
+    ( $x:ident $(,)? ) => {
+        $crate::declare_blueprint!(@impl $x {});
+    };
+    ( $x:ident, $name:literal $(,)? ) => {
+        $crate::declare_blueprint!(@impl $x { const NAME: Option<&'static str> = Some($name); });
+    };
+    ( $x:ident, $name:literal, package = $p:expr $(,)? ) => {
+        $crate::declare_blueprint!(@impl $x {
+            const NAME: Option<&'static str> = Some($name);
+            const PACKAGE: Option<PackageAddress> = Some($p);
+        });
+    };
+    ( $x:ident, package = $p:expr $(,)? ) => {
+        $crate::declare_blueprint!(@impl $x { const PACKAGE: Option<PackageAddress> = Some($p); });
+    };
+}
+
+/// Declares another name for a declared resource, ie. `declare_resource_alias!(WXRD = XRD)`
+///
+/// The alias is the same type, so `BucketOf<WXRD>` is a `BucketOf<XRD>`.  Use this instead of declaring a second resource
+/// with the same address, which `runtime_typechecks` rejects with `StaticTypeError::AddressAlreadyBound`.  The alias may
+/// have type parameters, ie. `declare_resource_alias!(XRD_LP<A> = LP<XRD, A>)`.
+///
+/// ```
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource_alias!(WXRD = XRD);
+/// declare_resource!(LP<A, B>, fungible);
+/// declare_resource_alias!(XRD_LP<A> = LP<XRD, A>);
+///
+/// fn wrap(xrd: BucketOf<XRD>) -> BucketOf<WXRD> {
+///     xrd
+/// }
+///
+/// fn pool(lp: BucketOf<LP<WXRD, XRD>>) -> BucketOf<XRD_LP<WXRD>> {
+///     lp
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_resource_alias {
+    ( $x:ident < $($g:ident),+ $(,)? > = $t:ty $(;)? ) => {
+        #[allow(non_camel_case_types)]
+        pub type $x<$($g),+> = $t;
+    };
+    ( $x:ident = $t:ty $(;)? ) => {
+        #[allow(non_camel_case_types)]
+        pub type $x = $t;
+        const _: fn() = || {
+            fn declared_resource<T: StaticResource>() {}
+            declared_resource::<$x>();
+        };
+    };
+}
+
+/// Declares a trait for the methods of another component, and a stub struct which calls them
+///
+/// The stub wraps a `ComponentAddress` and implements the trait by calling the component's method of the same name with
+/// `call_method_of`, so arguments like `BucketOf<RES>`, `ProofOf<RES>` and `ResourceOf<RES>` are encoded as the
+/// containers they wrap, and a typed return value is decoded with the same checks as `From<Bucket>`.  The trait can
+/// also be implemented by other types, ie. for a local mock.
+///
+/// ```
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(USDC);
+///
+/// typed_interface! {
+///     /// An exchange between XRD and USDC
+///     pub trait Exchange {
+///         fn swap(&self, input: BucketOf<XRD>) -> BucketOf<USDC>;
+///         fn price(&self) -> Decimal;
+///     }
+///     pub struct ExchangeComponent;
+/// }
+///
+/// fn buy(exchange: ComponentAddress, payment: BucketOf<XRD>) -> BucketOf<USDC> {
+///     ExchangeComponent::new(exchange).swap(payment)
+/// }
+/// ```
+///
+/// The stub's arguments and return value are as typed as the trait:
+/// ```compile_fail,E0308
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(USDC);
+///
+/// typed_interface! {
+///     pub trait Exchange {
+///         fn swap(&self, input: BucketOf<XRD>) -> BucketOf<USDC>;
+///     }
+///     pub struct ExchangeComponent;
+/// }
+///
+/// fn sell(exchange: ComponentAddress, payment: BucketOf<USDC>) -> BucketOf<XRD> {
+///     ExchangeComponent::new(exchange).swap(payment)
+/// }
+/// ```
+#[macro_export]
+macro_rules! typed_interface {
+    (
+        $(#[$tm:meta])*
+        $tv:vis trait $t:ident {
+            $(
+                $(#[$fm:meta])*
+                fn $f:ident(&self $(, $a:ident : $at:ty)* $(,)?) $(-> $r:ty)?;
+            )*
+        }
+        $(#[$sm:meta])*
+        $sv:vis struct $s:ident;
+    ) => {
+        $(#[$tm])*
+        $tv trait $t {
+            $(
+                $(#[$fm])*
+                fn $f(&self $(, $a: $at)*) $(-> $r)?;
+            )*
+        }
+
+        $(#[$sm])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $sv struct $s {
+            component: ComponentAddress,
+        }
+
+        impl $s {
+            /// Creates a stub for the component at this address.
+            #[inline(always)]
+            pub fn new(component: ComponentAddress) -> Self {
+                Self { component }
+            }
+
+            /// Returns the address of the component.
+            #[inline(always)]
+            pub fn address(&self) -> ComponentAddress {
+                self.component
+            }
+        }
+
+        impl From<ComponentAddress> for $s {
+            #[inline(always)]
+            fn from(component: ComponentAddress) -> Self {
+                Self::new(component)
+            }
         }
+
+        impl $t for $s {
+            $(
+                #[inline(always)]
+                fn $f(&self $(, $a: $at)*) $(-> $r)? {
+                    call_method_of(self.component, stringify!($f), vec![$(scrypto_encode(&$a)),*])
+                }
+            )*
+        }
+    };
+}
+
+/// Refers to a component field holding a `ResourceOf<RES>`, for access rules (see `FieldOf`)
+///
+/// `field_of!(Self, auth_def)` is a `FieldOf<RES>` of the field's resource, or pin it with `field_of!(Self, auth_def: AUTH)`.
+/// It only compiles while the field exists with type `ResourceOf<RES>`.
+///
+/// ```
+/// # #[macro_use] extern crate scrypto_statictypes;
+/// # fn main() {}
+/// use scrypto::prelude::*;
+/// use scrypto_statictypes::prelude::*;
+///
+/// declare_resource!(AUTH);
+///
+/// blueprint! {
+///     struct Burner {
+///         auth_def: ResourceOf<AUTH>,
+///     }
+///
+///     impl Burner {
+///         pub fn new(auth_def: ResourceOf<AUTH>) -> ComponentAddress {
+///             Self { auth_def }
+///                 .instantiate()
+///                 .add_access_check(
+///                     AccessRules::new()
+///                         .method("burn_it", rule!(require_of(field_of!(Self, auth_def: AUTH))))
+///                         .default(rule!(allow_all)),
+///                 )
+///                 .globalize()
+///         }
+///
+///         pub fn burn_it(&mut self) {}
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! field_of {
+    ( $component:ty, $field:ident ) => {
+        StaticFieldOf::of(|component: &$component| &component.$field, stringify!($field))
+    };
+    ( $component:ty, $field:ident : $res:ty ) => {
+        StaticFieldOf::<$res>::of(|component: &$component| &component.$field, stringify!($field))
+    };
+}
+
+/// An `AccessRule` requiring a proof of any of the resources, the typed `rule!(require_any_of(...))`
+///
+/// Each argument is a `&ResourceOf<RES>` or `FieldOf<RES>`, and the resources may differ, ie.
+/// `require_any_of!(&self.admin_def, field_of!(Self, auth_def))`.
+#[macro_export]
+macro_rules! require_any_of {
+    ( $($resource:expr),+ $(,)? ) => {
+        AccessRule::Protected(AccessRuleNode::ProofRule(require_any_of(vec![
+            $(StaticResourceRuleOf::soft_resource_or_non_fungible($resource)),+
+        ])))
+    };
+}
+
+/// An `AccessRule` requiring a proof of all of the resources, the typed `rule!(require_all_of(...))` (see `require_any_of!`)
+#[macro_export]
+macro_rules! require_all_of {
+    ( $($resource:expr),+ $(,)? ) => {
+        AccessRule::Protected(AccessRuleNode::ProofRule(require_all_of(vec![
+            $(StaticResourceRuleOf::soft_resource_or_non_fungible($resource)),+
+        ])))
+    };
+}
+
+/// Includes the `declare_resource!` declarations of a resource manifest, generated by `manifest::generate` in `build.rs`
+///
+/// The path must be the same one given to `generate`, relative to the crate root, see the `manifest` module (feature
+/// `manifest`) for the format.
+#[macro_export]
+macro_rules! include_resources {
+    ( $path:literal ) => {
+        include!(concat!(env!("OUT_DIR"), "/", $path, ".rs"));
     };
 }
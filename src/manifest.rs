@@ -0,0 +1,564 @@
+//! Resource manifests shared between crates and off-chain tooling
+//!
+//! A manifest lists declared resources by name, in TOML (or JSON) keyed by the name given to `declare_resource!`:
+//!
+//! ```toml
+//! [USDC]
+//! kind = "fungible"
+//! name = "USD Coin"
+//! symbol = "USDC"
+//! divisibility = 6
+//! local = "resource_sim1..."
+//! mainnet = "resource_rdx1..."
+//!
+//! [WXRD]
+//! address = "030000000000000000000000000000000000000000000000000004"
+//!
+//! [MEMBER]
+//! kind = "non_fungible"
+//! data = "MemberData"
+//!
+//! [MEMBER.metadata]
+//! url = "https://example.com"
+//! ```
+//!
+//! To declare the resources of a manifest, call `generate` from the crate's `build.rs` (with this crate as a
+//! build-dependency with the `manifest` feature)
+//!
+//! ```ignore
+//! fn main() {
+//!     scrypto_statictypes::manifest::generate("resources.toml").unwrap();
+//! }
+//! ```
+//!
+//! and include the declarations with `include_resources!("resources.toml")`.  To go the other way, build a manifest from
+//! a crate's declarations with `resource_manifest!(USDC, MEMBER)` and `save` it, ie. from a test, for front-ends and
+//! test harnesses to consume.  A parametrized resource is exported under a name made of its type, ie. `LP<XRD, USDC>`
+//! as `LP_XRD_USDC`, which declares a separate resource when the manifest is included.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::address::{encode_resource_address, Network};
+use crate::internal::{ResourceDecl, ResourceKind};
+
+/// A set of declared resources, by name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ResourceManifest {
+    pub resources: BTreeMap<String, ResourceEntry>,
+}
+
+/// One declared resource, the options of `declare_resource!`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResourceEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ManifestKind>,
+    /// Non-fungible data type, must be in scope where the declarations are included
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// Address on any network
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub testnet: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mainnet: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub divisibility: Option<u8>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Kind of a resource in a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestKind {
+    Fungible,
+    NonFungible,
+}
+
+/// Errors reading, writing or generating declarations from a manifest
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(std::io::Error),
+    Toml(String),
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl std::fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestError::Io(e) => write!(f, "resource manifest I/O error: {}", e),
+            ManifestError::Toml(e) => write!(f, "invalid resource manifest TOML: {}", e),
+            ManifestError::Json(e) => write!(f, "invalid resource manifest JSON: {}", e),
+            ManifestError::Invalid(e) => write!(f, "invalid resource manifest: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl From<std::io::Error> for ManifestError {
+    fn from(e: std::io::Error) -> Self {
+        ManifestError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ManifestError {
+    fn from(e: serde_json::Error) -> Self {
+        ManifestError::Json(e)
+    }
+}
+
+impl ResourceManifest {
+    /// Creates an empty manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the declaration of `RES` under `name`, with its address or all of its per-network addresses.
+    pub fn with<RES: ResourceDecl>(mut self, name: &str) -> Self {
+        self.resources.insert(name.to_string(), ResourceEntry::of::<RES>());
+        self
+    }
+
+    /// Parses a TOML manifest.
+    pub fn from_toml(s: &str) -> Result<Self, ManifestError> {
+        toml::from_str(s).map_err(|e| ManifestError::Toml(e.to_string()))
+    }
+
+    /// Parses a JSON manifest.
+    pub fn from_json(s: &str) -> Result<Self, ManifestError> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Formats as a TOML manifest.
+    pub fn to_toml(&self) -> Result<String, ManifestError> {
+        toml::to_string(self).map_err(|e| ManifestError::Toml(e.to_string()))
+    }
+
+    /// Formats as a JSON manifest.
+    pub fn to_json(&self) -> Result<String, ManifestError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Reads a manifest, JSON if the extension is `.json` otherwise TOML.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ManifestError> {
+        let contents = std::fs::read_to_string(path.as_ref())?;
+        if is_json(path.as_ref()) {
+            Self::from_json(&contents)
+        } else {
+            Self::from_toml(&contents)
+        }
+    }
+
+    /// Writes a manifest, JSON if the extension is `.json` otherwise TOML.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ManifestError> {
+        let contents = if is_json(path.as_ref()) { self.to_json()? } else { self.to_toml()? };
+        Ok(std::fs::write(path, contents)?)
+    }
+
+    /// Returns the `declare_resource!` declarations of every resource, one per line.
+    ///
+    /// Fails if two resources have the same address, an intentional alias must use `declare_resource_alias!` instead.
+    pub fn declarations(&self) -> Result<String, ManifestError> {
+        let mut addresses = BTreeMap::new();
+        for (name, entry) in &self.resources {
+            // the human readable part is different for each network, so the strings are unique across networks too
+            let entry_addresses = [&entry.address, &entry.local, &entry.testnet, &entry.mainnet];
+            for address in entry_addresses.into_iter().flatten() {
+                if let Some(other) = addresses.insert(address, name) {
+                    return Err(ManifestError::Invalid(format!(
+                        "{} and {} have the same address {}, use declare_resource_alias! for an alias",
+                        other, name, address
+                    )));
+                }
+            }
+        }
+        let mut declarations = String::new();
+        for (name, entry) in &self.resources {
+            declarations.push_str(&entry.declaration(name)?);
+            declarations.push('\n');
+        }
+        Ok(declarations)
+    }
+}
+
+impl ResourceEntry {
+    /// Creates the entry of a declared resource, with its address or all of its per-network addresses.
+    ///
+    /// An address declared for every network is written in hex, which is valid on any network.
+    pub fn of<RES: ResourceDecl>() -> Self {
+        let mut entry = ResourceEntry {
+            kind: match <RES::Kind as ResourceKind>::NAME {
+                Some("fungible") => Some(ManifestKind::Fungible),
+                Some(_) => Some(ManifestKind::NonFungible),
+                None => None,
+            },
+            // without the module paths, which are not valid from inside the declaring crate
+            data: <RES::Kind as ResourceKind>::data_type_name().map(without_paths),
+            name: RES::NAME.map(str::to_string),
+            symbol: RES::SYMBOL.map(str::to_string),
+            divisibility: RES::DIVISIBILITY,
+            metadata: RES::METADATA.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            ..Default::default()
+        };
+        for (network, address) in RES::ADDRESSES {
+            let encoded = Some(encode_resource_address(*network, address));
+            match network {
+                Network::Local => entry.local = encoded,
+                Network::Testnet => entry.testnet = encoded,
+                Network::Mainnet => entry.mainnet = encoded,
+            }
+        }
+        if RES::ADDRESSES.is_empty() {
+            entry.address = RES::ADDRESS.map(|address| address.to_string());
+        }
+        entry
+    }
+
+    /// Returns the `declare_resource!` declaration of this resource as `name`.
+    pub fn declaration(&self, name: &str) -> Result<String, ManifestError> {
+        if !is_identifier(name) {
+            return Err(ManifestError::Invalid(format!("{:?} is not a valid resource name", name)));
+        }
+        let networks = [("local", &self.local), ("testnet", &self.testnet), ("mainnet", &self.mainnet)];
+        if self.address.is_some() && networks.iter().any(|(_, address)| address.is_some()) {
+            return Err(ManifestError::Invalid(format!(
+                "{} has both an address and per-network addresses",
+                name
+            )));
+        }
+
+        let mut options = vec![name.to_string()];
+        if let Some(address) = &self.address {
+            options.push(format!("{:?}", address)); // must be the first option
+        }
+        match (self.kind, &self.data) {
+            (Some(ManifestKind::Fungible), Some(_)) => {
+                return Err(ManifestError::Invalid(format!("{} is fungible but has a non-fungible data type", name)))
+            }
+            (Some(ManifestKind::Fungible), None) => options.push("fungible".to_string()),
+            (_, Some(data)) => options.push(format!("non_fungible = {}", data)),
+            (Some(ManifestKind::NonFungible), None) => options.push("non_fungible".to_string()),
+            (None, None) => {}
+        }
+        if let Some(v) = &self.name {
+            options.push(format!("name = {:?}", v));
+        }
+        if let Some(v) = &self.symbol {
+            options.push(format!("symbol = {:?}", v));
+        }
+        if let Some(v) = self.divisibility {
+            options.push(format!("divisibility = {}", v));
+        }
+        if !self.metadata.is_empty() {
+            let metadata: Vec<String> = self.metadata.iter().map(|(k, v)| format!("{:?} => {:?}", k, v)).collect();
+            options.push(format!("metadata = {{ {} }}", metadata.join(", ")));
+        }
+        for (network, address) in networks {
+            if let Some(address) = address {
+                options.push(format!("{} = {:?}", network, address));
+            }
+        }
+        Ok(format!("declare_resource!({});", options.join(", ")))
+    }
+}
+
+/// Generates the declarations of a manifest for `include_resources!`, to be called from `build.rs`
+///
+/// The manifest path is relative to the crate root (without `..`), and must be the same one given to
+/// `include_resources!`.
+pub fn generate<P: AsRef<Path>>(manifest: P) -> Result<(), ManifestError> {
+    let manifest = manifest.as_ref();
+    println!("cargo:rerun-if-changed={}", manifest.display());
+    let out_dir = std::env::var_os("OUT_DIR")
+        .ok_or_else(|| ManifestError::Invalid("OUT_DIR is not set, generate must be called from build.rs".to_string()))?;
+    generate_in(manifest, Path::new(&out_dir))
+}
+
+fn generate_in(manifest: &Path, out_dir: &Path) -> Result<(), ManifestError> {
+    let out = declarations_path(manifest, out_dir)?;
+    let declarations = ResourceManifest::load(manifest)?.declarations()?;
+    if let Some(parent) = out.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(std::fs::write(out, declarations)?)
+}
+
+// OUT_DIR/<manifest>.rs, the path include_resources! includes, so the manifest path must stay inside OUT_DIR
+fn declarations_path(manifest: &Path, out_dir: &Path) -> Result<PathBuf, ManifestError> {
+    if !manifest.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir)) {
+        return Err(ManifestError::Invalid(format!(
+            "{} must be a path relative to the crate root without ..",
+            manifest.display()
+        )));
+    }
+    let mut out = out_dir.join(manifest).into_os_string();
+    out.push(".rs");
+    Ok(PathBuf::from(out))
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().map_or(false, |extension| extension == "json")
+}
+
+// ie. my_crate::data::Wrapper<my_crate::data::Member> is Wrapper<Member>
+fn without_paths(type_name: &str) -> String {
+    let mut name = String::with_capacity(type_name.len());
+    let mut segment = 0; // start of the current path in name
+    let mut chars = type_name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            name.truncate(segment);
+        } else {
+            name.push(c);
+            if !(c == '_' || c.is_ascii_alphanumeric()) {
+                segment = name.len();
+            }
+        }
+    }
+    name
+}
+
+/// Returns the name a type is exported under by `resource_manifest!`, ie. `LP<XRD, USDC>` is `LP_XRD_USDC`
+pub fn manifest_name(type_name: &str) -> String {
+    let words: Vec<&str> = type_name.split(|c: char| !(c == '_' || c.is_ascii_alphanumeric())).collect();
+    words.into_iter().filter(|word| !word.is_empty()).collect::<Vec<_>>().join("_")
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// Builds a `ResourceManifest` from declared resources, ie. `resource_manifest!(XRD, MYTOKEN).save("resources.toml")`
+///
+/// Each resource is named after its type, with `manifest_name`.
+#[macro_export]
+macro_rules! resource_manifest {
+    ( $($x:ty),* $(,)? ) => {
+        $crate::manifest::ResourceManifest::new() $(.with::<$x>(&$crate::manifest::manifest_name(stringify!($x))))*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::{parse_network_resource_address, parse_resource_address};
+    use crate::internal::{AddressSelected, FungibleKind, NonFungibleKindOf, Resource, ResourceAddress};
+
+    const LOCAL: &str = "resource_sim1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydqrwhcyc";
+    const MAINNET: &str = "resource_rdx1qvqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydq9vjp08";
+    const HEX: &str = "030000000000000000000000000000000000000000000000000004";
+
+    mod data {
+        #[derive(Debug)]
+        pub struct MemberData;
+        #[derive(Debug)]
+        pub struct Wrapper<T>(std::marker::PhantomData<T>);
+    }
+
+    #[derive(Debug)]
+    enum Usdc {}
+    impl Resource for Usdc {}
+    impl ResourceDecl for Usdc {
+        const ADDRESS: Option<ResourceAddress> = Some(parse_network_resource_address(Network::Local, LOCAL));
+        const ADDRESSES: &'static [(Network, ResourceAddress)] = &[
+            (Network::Local, parse_network_resource_address(Network::Local, LOCAL)),
+            (Network::Mainnet, parse_network_resource_address(Network::Mainnet, MAINNET)),
+        ];
+        type Kind = FungibleKind;
+        type Addressed = AddressSelected<true>;
+        const NAME: Option<&'static str> = Some("USD Coin");
+        const SYMBOL: Option<&'static str> = Some("USDC");
+        const DIVISIBILITY: Option<u8> = Some(6);
+    }
+
+    #[derive(Debug)]
+    enum Wxrd {}
+    impl Resource for Wxrd {}
+    impl ResourceDecl for Wxrd {
+        const ADDRESS: Option<ResourceAddress> = Some(parse_resource_address(HEX));
+        type Kind = FungibleKind;
+        type Addressed = AddressSelected<true>;
+    }
+
+    #[derive(Debug)]
+    enum Member {}
+    impl Resource for Member {}
+    impl ResourceDecl for Member {
+        const ADDRESS: Option<ResourceAddress> = None;
+        type Kind = NonFungibleKindOf<data::Wrapper<data::MemberData>>;
+        type Addressed = AddressSelected<false>;
+        const METADATA: &'static [(&'static str, &'static str)] = &[("url", "https://example.com")];
+    }
+
+    #[test]
+    fn test_parse() {
+        let toml = r#"
+            [USDC]
+            kind = "fungible"
+            divisibility = 6
+            local = "resource_sim1..."
+
+            [MEMBER]
+            data = "MemberData"
+
+            [MEMBER.metadata]
+            url = "https://example.com"
+        "#;
+        let json = r#"{
+            "USDC": { "kind": "fungible", "divisibility": 6, "local": "resource_sim1..." },
+            "MEMBER": { "data": "MemberData", "metadata": { "url": "https://example.com" } }
+        }"#;
+        let manifest = ResourceManifest::from_toml(toml).unwrap();
+        assert_eq!(manifest, ResourceManifest::from_json(json).unwrap());
+        let usdc = &manifest.resources["USDC"];
+        assert_eq!(usdc.kind, Some(ManifestKind::Fungible));
+        assert_eq!(usdc.divisibility, Some(6));
+        assert_eq!(usdc.local.as_deref(), Some("resource_sim1..."));
+        assert_eq!(manifest.resources["MEMBER"].metadata["url"], "https://example.com");
+
+        assert!(matches!(ResourceManifest::from_toml("[USDC]\nkind = \"fungibel\""), Err(ManifestError::Toml(_))));
+        assert!(matches!(ResourceManifest::from_toml("[USDC]\nsymbl = \"USDC\""), Err(ManifestError::Toml(_))));
+    }
+
+    #[test]
+    fn test_declarations() {
+        let manifest = ResourceManifest::from_toml(&format!(
+            "[USDC]\nkind = \"fungible\"\nsymbol = \"USDC\"\nlocal = {:?}\nmainnet = {:?}\n\n[WXRD]\naddress = {:?}\n\n\
+             [MEMBER]\ndata = \"MemberData\"\n\n[MEMBER.metadata]\nurl = \"https://example.com\"\n",
+            LOCAL, MAINNET, HEX
+        ))
+        .unwrap();
+        let expected = [
+            r#"declare_resource!(MEMBER, non_fungible = MemberData, metadata = { "url" => "https://example.com" });"#
+                .to_string(),
+            format!(r#"declare_resource!(USDC, fungible, symbol = "USDC", local = {:?}, mainnet = {:?});"#, LOCAL, MAINNET),
+            format!(r#"declare_resource!(WXRD, {:?});"#, HEX),
+        ];
+        assert_eq!(manifest.declarations().unwrap(), expected.join("\n") + "\n");
+    }
+
+    #[test]
+    fn test_declarations_invalid() {
+        let invalid = [
+            format!("[USDC]\naddress = {:?}\nlocal = {:?}\n", HEX, LOCAL),
+            "[USDC]\nkind = \"fungible\"\ndata = \"MemberData\"\n".to_string(),
+            "[\"USD-C\"]\nkind = \"fungible\"\n".to_string(),
+        ];
+        for toml in invalid {
+            let manifest = ResourceManifest::from_toml(&toml).unwrap();
+            assert!(matches!(manifest.declarations(), Err(ManifestError::Invalid(_))), "{}", toml);
+        }
+    }
+
+    #[test]
+    fn test_declarations_duplicate_address() {
+        let duplicates = [
+            format!("[WXRD]\naddress = {:?}\n\n[XRD]\naddress = {:?}\n", HEX, HEX),
+            format!("[USDC]\nlocal = {:?}\n\n[USDT]\nlocal = {:?}\n", LOCAL, LOCAL),
+            format!("[USDC]\nmainnet = {:?}\n\n[USDT]\nlocal = {:?}\nmainnet = {:?}\n", MAINNET, LOCAL, MAINNET),
+        ];
+        for toml in duplicates {
+            let manifest = ResourceManifest::from_toml(&toml).unwrap();
+            match manifest.declarations() {
+                Err(ManifestError::Invalid(e)) => assert!(e.contains("declare_resource_alias!"), "{}", e),
+                result => panic!("duplicate address not detected: {:?}\n{}", result, toml),
+            }
+        }
+
+        // the same resource on several networks is not a duplicate, nor are different resources
+        let toml = format!("[USDC]\nlocal = {:?}\nmainnet = {:?}\n\n[WXRD]\naddress = {:?}\n", LOCAL, MAINNET, HEX);
+        assert!(ResourceManifest::from_toml(&toml).unwrap().declarations().is_ok());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let manifest = ResourceManifest::new().with::<Usdc>("USDC").with::<Wxrd>("WXRD").with::<Member>("MEMBER");
+        let usdc = &manifest.resources["USDC"];
+        assert_eq!(usdc.address, None);
+        assert_eq!(usdc.local.as_deref(), Some(LOCAL));
+        assert_eq!(usdc.testnet, None);
+        assert_eq!(usdc.mainnet.as_deref(), Some(MAINNET));
+        assert_eq!(manifest.resources["WXRD"].address.as_deref(), Some(HEX));
+        assert_eq!(manifest.resources["MEMBER"].data.as_deref(), Some("Wrapper<MemberData>"));
+
+        let toml = ResourceManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(toml, manifest);
+        let json = ResourceManifest::from_json(&manifest.to_json().unwrap()).unwrap();
+        assert_eq!(json, manifest);
+        assert_eq!(toml.declarations().unwrap(), manifest.declarations().unwrap());
+        assert!(manifest.declarations().unwrap().contains(
+            r#"declare_resource!(MEMBER, non_fungible = Wrapper<MemberData>, metadata = { "url" => "https://example.com" });"#
+        ));
+    }
+
+    #[test]
+    fn test_without_paths() {
+        assert_eq!(without_paths("MemberData"), "MemberData");
+        assert_eq!(without_paths("my_crate::data::MemberData"), "MemberData");
+        assert_eq!(without_paths("a::Wrapper<b::c::MemberData>"), "Wrapper<MemberData>");
+        assert_eq!(without_paths("a::Pair<b::X, (c::Y, u8)>"), "Pair<X, (Y, u8)>");
+    }
+
+    #[test]
+    fn test_manifest_name() {
+        assert_eq!(manifest_name("USDC"), "USDC");
+        assert_eq!(manifest_name("LP<XRD, USDC>"), "LP_XRD_USDC");
+        assert_eq!(manifest_name("LP < XRD , USDC >"), "LP_XRD_USDC");
+        assert_eq!(manifest_name("LP<LP<XRD, USDC>, MY_TOKEN>"), "LP_LP_XRD_USDC_MY_TOKEN");
+        assert_eq!(
+            ResourceManifest::new().with::<Usdc>(&manifest_name(stringify!(LP<XRD, USDC>))).resources.keys().next(),
+            Some(&"LP_XRD_USDC".to_string())
+        );
+    }
+
+    #[test]
+    fn test_declarations_path() {
+        let out_dir = Path::new("out");
+        assert_eq!(declarations_path(Path::new("resources.toml"), out_dir).unwrap(), out_dir.join("resources.toml.rs"));
+        assert_eq!(
+            declarations_path(Path::new("manifests/resources.json"), out_dir).unwrap(),
+            out_dir.join("manifests").join("resources.json.rs")
+        );
+        for escaping in ["../resources.toml", "manifests/../../resources.toml", "/etc/resources.toml"] {
+            assert!(
+                matches!(declarations_path(Path::new(escaping), out_dir), Err(ManifestError::Invalid(_))),
+                "{}",
+                escaping
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        // relative to the crate root, the working directory of the tests
+        let manifest = Path::new("target/manifest-test/resources.toml");
+        std::fs::create_dir_all(manifest.parent().unwrap()).unwrap();
+        ResourceManifest::new().with::<Usdc>("USDC").save(manifest).unwrap();
+
+        let out_dir = std::env::temp_dir().join(format!("scrypto_statictypes-manifest-{}", std::process::id()));
+        generate_in(manifest, &out_dir).unwrap();
+        let generated = std::fs::read_to_string(out_dir.join("target/manifest-test/resources.toml.rs")).unwrap();
+        assert_eq!(generated, ResourceManifest::load(manifest).unwrap().declarations().unwrap());
+        assert!(generated.starts_with("declare_resource!(USDC, fungible, "));
+
+        assert!(matches!(generate_in(Path::new("../resources.toml"), &out_dir), Err(ManifestError::Invalid(_))));
+        std::fs::remove_dir_all(out_dir).unwrap();
+    }
+}
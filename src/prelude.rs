@@ -7,14 +7,26 @@
 //! ```
 //! use scrypto_statictypes::prelude::*;
 //! ```
+pub use crate::accessrules::{require_amount_of, require_of, FieldOf, ResourceRuleOf};
+pub use crate::amountof::{AmountOf, IntoAmountOf, Rounding};
+pub use crate::authzoneof::AuthZoneOf;
 pub use crate::bucketof::BucketOf;
+pub use crate::componentof::ComponentOf;
 pub use crate::proofof::{ProofOf, UncheckedIntoProofOf};
-pub use crate::declare_resource; /* this is for the macros themselves, and must be explicitly named (at top level due to #[macro_export]) */
+pub use crate::{
+    declare_blueprint, declare_resource, declare_resource_alias, field_of, include_resources, require_all_of, require_any_of,
+    typed_interface,
+}; /* this is for the macros themselves, and must be explicitly named (at top level due to #[macro_export]) */
+pub use crate::error::{ConversionError, FailurePolicy, StaticTypeError};
 pub use crate::exts::{
-    Deposit, DepositOf, DepositOfExplicit, Withdraw, WithdrawOf, WithdrawOfExplicit,
+    call_function_of, call_method_of, Account, Deposit, DepositOf, DepositOfExplicit, Withdraw, WithdrawOf, WithdrawOfExplicit,
 };
-pub use crate::internal::{UncheckedInto, Unwrap, WithInner}; /* to access trait methods with_inner(...) and unchecked_into() */
+pub use crate::internal::{TryIntoOf, UncheckedInto, Unwrap, WithInner}; /* to access trait methods with_inner(...), unchecked_into() and try_into_of() */
+pub use crate::internal::{AddressedResource, FungibleResource, NonFungibleDataResource, NonFungibleResource}; /* for generic code over resource kinds */
 pub use crate::macros::*; /* this is for things a macro might generate */
+pub use crate::price::Price;
+pub use crate::registry::ResourceBinding; /* for MYTOKEN::bind(address) */
+pub use crate::resourcebuilderof::{FungibleResourceBuilderOf, NonFungibleResourceBuilderOf, ResourceBuilderOf};
 pub use crate::resourceof::ResourceOf;
 pub use crate::vaultof::VaultOf;
 pub use crate::XRD;
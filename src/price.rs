@@ -0,0 +1,336 @@
+use std::marker::PhantomData;
+use std::ops::{Div, Mul};
+
+use scrypto::prelude::*;
+
+use crate::amountof::*;
+use crate::internal::*;
+use crate::resourceof::ResourceOf;
+
+/// An exchange rate between two declared resources, in units of `QUOTE` per one unit of `BASE`
+///
+/// Multiplying an `AmountOf<BASE>` by a `Price<BASE, QUOTE>` gives an `AmountOf<QUOTE>`, and dividing an
+/// `AmountOf<QUOTE>` by it gives back an `AmountOf<BASE>`.  Prices compose, `Price<A, B> * Price<B, C>` is a
+/// `Price<A, C>`, so the compiler checks every conversion in a chain of swaps.
+///
+/// The arithmetic operators keep full `Decimal` precision, use `convert` or `convert_for` to round the result
+/// to the divisibility of `QUOTE`.
+pub struct Price<BASE, QUOTE> {
+    pub(crate) rate: Decimal,
+    pub(crate) phantom: PhantomData<(BASE, QUOTE)>,
+}
+
+impl<BASE: Resource, QUOTE: Resource> Price<BASE, QUOTE> {
+    /// Creates a price of `rate` units of `QUOTE` per unit of `BASE`.
+    #[inline(always)]
+    pub fn new<A: Into<Decimal>>(rate: A) -> Self {
+        Self {
+            rate: rate.into(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Creates the price at which `base` is exchanged for `quote`.
+    ///
+    /// # Panics
+    /// Panics if `base` is zero.
+    #[inline(always)]
+    pub fn from_amounts(base: AmountOf<BASE>, quote: AmountOf<QUOTE>) -> Self {
+        assert!(!base.is_zero(), "Price from zero amount");
+        Self::new(quote.unwrap() / base.unwrap())
+    }
+
+    /// Returns the number of units of `QUOTE` per unit of `BASE`.
+    #[inline(always)]
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+
+    /// Returns the inverse price, in units of `BASE` per unit of `QUOTE`.
+    ///
+    /// # Panics
+    /// Panics if the rate is zero.
+    #[inline(always)]
+    pub fn inverse(&self) -> Price<QUOTE, BASE> {
+        assert!(!self.rate.is_zero(), "Inverse of zero Price");
+        Price::<QUOTE, BASE>::new(Decimal::one() / self.rate)
+    }
+
+    /// Converts an amount of `BASE` to `QUOTE`, rounded to the given divisibility of `QUOTE`.
+    #[inline(always)]
+    pub fn convert(&self, amount: AmountOf<BASE>, divisibility: u8, mode: Rounding) -> AmountOf<QUOTE> {
+        (amount * *self).round(divisibility, mode)
+    }
+
+    /// Converts an amount of `BASE` to `QUOTE`, rounded to the divisibility of the `QUOTE` resource on ledger.
+    #[inline(always)]
+    pub fn convert_for(&self, amount: AmountOf<BASE>, quote: &ResourceOf<QUOTE>, mode: Rounding) -> AmountOf<QUOTE> {
+        let divisibility = match quote.resource_type() {
+            ResourceType::Fungible { divisibility } => divisibility,
+            ResourceType::NonFungible => 0,
+        };
+        self.convert(amount, divisibility, mode)
+    }
+}
+
+impl<BASE: Resource, QUOTE: ResourceDecl> Price<BASE, QUOTE> {
+    /// Converts an amount of `BASE` to `QUOTE`, rounded to the divisibility declared for `QUOTE` (or the maximum if not declared).
+    #[inline(always)]
+    pub fn convert_declared(&self, amount: AmountOf<BASE>, mode: Rounding) -> AmountOf<QUOTE> {
+        self.convert(amount, QUOTE::DIVISIBILITY.unwrap_or(DIVISIBILITY_MAXIMUM), mode)
+    }
+}
+
+// AmountOf<BASE> * Price<BASE, QUOTE> = AmountOf<QUOTE>
+impl<BASE: Resource, QUOTE: Resource> Mul<Price<BASE, QUOTE>> for AmountOf<BASE> {
+    type Output = AmountOf<QUOTE>;
+
+    #[inline(always)]
+    fn mul(self, price: Price<BASE, QUOTE>) -> Self::Output {
+        AmountOf::<QUOTE>::new(self.unwrap() * price.rate)
+    }
+}
+
+// Price<BASE, QUOTE> * AmountOf<BASE> = AmountOf<QUOTE>
+impl<BASE: Resource, QUOTE: Resource> Mul<AmountOf<BASE>> for Price<BASE, QUOTE> {
+    type Output = AmountOf<QUOTE>;
+
+    #[inline(always)]
+    fn mul(self, amount: AmountOf<BASE>) -> Self::Output {
+        amount * self
+    }
+}
+
+// AmountOf<QUOTE> / Price<BASE, QUOTE> = AmountOf<BASE>
+impl<BASE: Resource, QUOTE: Resource> Div<Price<BASE, QUOTE>> for AmountOf<QUOTE> {
+    type Output = AmountOf<BASE>;
+
+    #[inline(always)]
+    fn div(self, price: Price<BASE, QUOTE>) -> Self::Output {
+        AmountOf::<BASE>::new(self.unwrap() / price.rate)
+    }
+}
+
+// Price<A, B> * Price<B, C> = Price<A, C>
+impl<A: Resource, B: Resource, C: Resource> Mul<Price<B, C>> for Price<A, B> {
+    type Output = Price<A, C>;
+
+    #[inline(always)]
+    fn mul(self, rhs: Price<B, C>) -> Self::Output {
+        Price::<A, C>::new(self.rate * rhs.rate)
+    }
+}
+
+// manual impls instead of derive, since derive would require the same traits on the (uninhabited) BASE and QUOTE
+
+impl<BASE: Resource, QUOTE: Resource> Clone for Price<BASE, QUOTE> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<BASE: Resource, QUOTE: Resource> Copy for Price<BASE, QUOTE> {}
+
+impl<BASE: Resource, QUOTE: Resource> PartialEq for Price<BASE, QUOTE> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.rate == other.rate
+    }
+}
+
+impl<BASE: Resource, QUOTE: Resource> Eq for Price<BASE, QUOTE> {}
+
+impl<BASE: Resource, QUOTE: Resource> PartialOrd for Price<BASE, QUOTE> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.rate.partial_cmp(&other.rate)
+    }
+}
+
+impl<BASE: Resource, QUOTE: Resource> std::fmt::Debug for Price<BASE, QUOTE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} {}/{}",
+            self.rate,
+            std::any::type_name::<QUOTE>(),
+            std::any::type_name::<BASE>()
+        )
+    }
+}
+
+impl<BASE: Resource, QUOTE: Resource> std::fmt::Display for Price<BASE, QUOTE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.rate, f)
+    }
+}
+
+//=====
+// SBOR
+//=====
+
+// Price looks exactly like a Decimal, there is nothing to check when decoding
+
+impl<BASE: Resource, QUOTE: Resource> sbor::TypeId for Price<BASE, QUOTE> {
+    #[inline(always)]
+    fn type_id() -> u8 {
+        <Decimal as sbor::TypeId>::type_id()
+    }
+}
+
+impl<BASE: Resource, QUOTE: Resource> sbor::Describe for Price<BASE, QUOTE> {
+    #[inline(always)]
+    fn describe() -> sbor::describe::Type {
+        <Decimal as sbor::Describe>::describe()
+    }
+}
+
+impl<BASE: Resource, QUOTE: Resource> sbor::Encode for Price<BASE, QUOTE> {
+    #[inline(always)]
+    fn encode_value(&self, encoder: &mut sbor::Encoder) {
+        <Decimal as sbor::Encode>::encode_value(&self.rate, encoder)
+    }
+}
+
+impl<BASE: Resource, QUOTE: Resource> sbor::Decode for Price<BASE, QUOTE> {
+    #[inline(always)]
+    fn decode_value(decoder: &mut sbor::Decoder) -> Result<Self, sbor::DecodeError> {
+        <Decimal as sbor::Decode>::decode_value(decoder).map(Price::<BASE, QUOTE>::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[derive(Debug)]
+    enum Base {}
+    impl Resource for Base {}
+
+    #[derive(Debug)]
+    enum Quote {}
+    impl Resource for Quote {}
+    impl ResourceDecl for Quote {
+        const ADDRESS: Option<ResourceAddress> = None;
+        type Kind = FungibleKind;
+        type Addressed = AddressSelected<false>;
+        const DIVISIBILITY: Option<u8> = Some(2);
+    }
+
+    use crate::amountof::Rounding::*;
+
+    fn d(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_round_decimal() {
+        let cases = [
+            // exact values are never changed
+            ("1.2", 1, AwayFromZero, "1.2"),
+            ("-1.2", 1, TowardsZero, "-1.2"),
+            ("123.456", 18, AwayFromZero, "123.456"),
+            // every mode, positive
+            ("1.25", 1, TowardsZero, "1.2"),
+            ("1.25", 1, AwayFromZero, "1.3"),
+            ("1.25", 1, TowardsNegativeInfinity, "1.2"),
+            ("1.25", 1, TowardsPositiveInfinity, "1.3"),
+            ("1.25", 1, TowardsNearestAndHalfAwayFromZero, "1.3"),
+            ("1.24", 1, TowardsNearestAndHalfAwayFromZero, "1.2"),
+            // every mode, negative
+            ("-1.25", 1, TowardsZero, "-1.2"),
+            ("-1.25", 1, AwayFromZero, "-1.3"),
+            ("-1.25", 1, TowardsNegativeInfinity, "-1.3"),
+            ("-1.25", 1, TowardsPositiveInfinity, "-1.2"),
+            ("-1.25", 1, TowardsNearestAndHalfAwayFromZero, "-1.3"),
+            ("-1.24", 1, TowardsNearestAndHalfAwayFromZero, "-1.2"),
+            // divisibility 0, including values between -1 and 1
+            ("9.99", 0, TowardsZero, "9"),
+            ("0.000000000000000001", 0, AwayFromZero, "1"),
+            ("0.000000000000000001", 0, TowardsNegativeInfinity, "0"),
+            ("-0.000000000000000001", 0, TowardsZero, "0"),
+            ("-0.000000000000000001", 0, AwayFromZero, "-1"),
+            ("-0.000000000000000001", 0, TowardsNegativeInfinity, "-1"),
+            ("-0.5", 0, TowardsNearestAndHalfAwayFromZero, "-1"),
+            ("-0.4", 0, TowardsNearestAndHalfAwayFromZero, "0"),
+            // other divisibilities
+            ("1.23456", 3, TowardsPositiveInfinity, "1.235"),
+            ("-1.23456", 3, TowardsPositiveInfinity, "-1.234"),
+            ("1.000000000000000001", 17, AwayFromZero, "1.00000000000000001"),
+        ];
+        for (value, divisibility, mode, expected) in cases {
+            assert_eq!(
+                round_decimal(d(value), divisibility, mode),
+                d(expected),
+                "round_decimal({}, {}, {:?})",
+                value,
+                divisibility,
+                mode
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid divisibility 19")]
+    fn test_round_decimal_invalid_divisibility() {
+        round_decimal(d("1"), 19, TowardsZero);
+    }
+
+    #[test]
+    fn test_convert_rounds_to_divisibility() {
+        let price = Price::<Base, Quote>::new(d("1.5"));
+        let amount = AmountOf::<Base>::new(d("1.25")); // 1.875 Quote
+        let cases = [
+            (2, TowardsZero, "1.87"),
+            (2, AwayFromZero, "1.88"),
+            (2, TowardsNearestAndHalfAwayFromZero, "1.88"),
+            (1, TowardsNearestAndHalfAwayFromZero, "1.9"),
+            (0, TowardsNegativeInfinity, "1"),
+            (18, TowardsZero, "1.875"),
+        ];
+        for (divisibility, mode, expected) in cases {
+            assert_eq!(price.convert(amount, divisibility, mode).unwrap(), d(expected));
+        }
+        let negative = Price::<Base, Quote>::new(d("-1.5"));
+        assert_eq!(
+            negative.convert(amount, 2, TowardsNegativeInfinity).unwrap(),
+            d("-1.88")
+        );
+        assert_eq!(negative.convert(amount, 2, AwayFromZero).unwrap(), d("-1.88"));
+        assert_eq!(negative.convert(amount, 2, TowardsZero).unwrap(), d("-1.87"));
+        // Quote is declared with divisibility 2
+        assert_eq!(price.convert_declared(amount, TowardsZero).unwrap(), d("1.87"));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let cases = [("4", "0.25"), ("0.5", "2"), ("-8", "-0.125"), ("1", "1")];
+        for (rate, inverse) in cases {
+            let price = Price::<Base, Quote>::new(d(rate));
+            assert_eq!(price.inverse().rate(), d(inverse), "inverse of {}", rate);
+            assert_eq!(price.inverse().inverse(), price, "inverse of inverse of {}", rate);
+        }
+        // dividing by a price is multiplying by its inverse
+        let price = Price::<Base, Quote>::new(d("4"));
+        let quote = AmountOf::<Quote>::new(d("10"));
+        assert_eq!(quote / price, quote * price.inverse());
+        assert_eq!((quote / price).unwrap(), d("2.5"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Inverse of zero Price")]
+    fn test_inverse_of_zero() {
+        Price::<Base, Quote>::new(Decimal::zero()).inverse();
+    }
+
+    #[test]
+    fn test_from_amounts() {
+        let price = Price::from_amounts(AmountOf::<Base>::new(2), AmountOf::<Quote>::new(3));
+        assert_eq!(price.rate(), d("1.5"));
+        assert_eq!(AmountOf::<Base>::new(2) * price, AmountOf::<Quote>::new(3));
+        assert_eq!(AmountOf::<Quote>::new(3) / price, AmountOf::<Base>::new(2));
+    }
+}
@@ -3,6 +3,7 @@ use std::marker::PhantomData;
 
 use scrypto::prelude::*;
 
+use crate::amountof::*;
 use crate::internal::*;
 use crate::resourceof::ResourceOf;
 
@@ -26,6 +27,17 @@ impl_SBOR_traits_without_Encode_Decode!(ProofOf<RES>, Proof);
 #[cfg(feature = "runtime_typechecks")]
 impl_SBOR_Decode!(ProofOf<RES>, Proof);
 
+// without runtime_typechecks only resources declared with an address can be checked, so only they can be decoded (ie. as
+// a method argument) and ProofOf<RES> of any other resource stays a compile error
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: AddressedResource> sbor::Decode for ProofOf<RES> {
+    #[inline(always)]
+    fn decode_value(decoder: &mut sbor::Decoder) -> Result<Self, sbor::DecodeError> {
+        let inner = <Proof as sbor::Decode>::decode_value(decoder)?;
+        TryIntoOf::<RES, ProofOf<RES>>::try_into_of(inner).map_err(decode_error)
+    }
+}
+
 impl SBORable for Proof {}
 impl Container for Proof {}
 impl_HasResourceAddress!(Proof);
@@ -66,31 +78,58 @@ impl<RES: Resource> ProofOf<RES> {
     }
 }
 
-// custom impl From<Proof> with runtime checks
+// custom impl TryIntoOf<Proof> with runtime checks
 #[cfg(feature = "runtime_typechecks")]
-impl<RES: runtimechecks::Resource> From<Proof> for ProofOf<RES> {
-    fn from(proof: Proof) -> Self {
-        if !runtimechecks::check_address::<RES>(proof.resource_address()) {
-            // not sure a better error here as with BucketOf and VaultOf
-            panic!("Proof mismatch");
+impl<RES: runtimechecks::Resource> TryIntoOf<RES, ProofOf<RES>> for Proof {
+    fn try_into_of(self) -> Result<ProofOf<RES>, ConversionError<Self>> {
+        if let Err(error) = runtimechecks::check_address::<RES>(self.resource_address()) {
+            return Err(ConversionError { value: self, error });
         }
-        if proof.amount() <= 0.into() {
+        if self.amount() <= 0.into() {
             // check() and contains() both check the amount, choosing to keep these semantics
-            panic!("Will not create empty ProofOf");
+            let error = StaticTypeError::EmptyProof {
+                resource: std::any::type_name::<RES>(),
+            };
+            return Err(ConversionError { value: self, error });
         }
-        UncheckedIntoProofOf::unchecked_into(proof)
+        Ok(UncheckedIntoProofOf::unchecked_into(self))
     }
 }
 
-// choosing to implement this with panic! instead of unchecked_into because Proof is used for autnentication and silently converting at runtime is worse than
+// choosing to fail instead of unchecked_into because Proof is used for autnentication and silently converting at runtime is worse than
 // with other types like Vault and Bucket where there is more benefit to allow gradual typing
+// (a declared address is a cheap constant comparison, so those are checked the same as with runtime checks)
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: ResourceDecl> TryIntoOf<RES, ProofOf<RES>> for Proof {
+    fn try_into_of(self) -> Result<ProofOf<RES>, ConversionError<Self>> {
+        let resource = std::any::type_name::<RES>();
+        if RES::ADDRESS.is_none() {
+            return Err(ConversionError { value: self, error: StaticTypeError::Unchecked { resource } });
+        }
+        if let Err(error) = check_declared_address::<RES>(self.resource_address()) {
+            return Err(ConversionError { value: self, error });
+        }
+        if self.amount() <= 0.into() {
+            // same semantics as with runtime checks
+            return Err(ConversionError { value: self, error: StaticTypeError::EmptyProof { resource } });
+        }
+        Ok(UncheckedIntoProofOf::unchecked_into(self))
+    }
+}
+
 // custom impl From<Proof> since we can't use impl_wrapper_struct! for ProofOf
+#[cfg(feature = "runtime_typechecks")]
+impl<RES: runtimechecks::Resource> From<Proof> for ProofOf<RES> {
+    fn from(proof: Proof) -> Self {
+        proof.try_into_of().unwrap_or_else(|e| panic!("Proof mismatch: {}", e))
+    }
+}
+
 #[cfg(not(feature = "runtime_typechecks"))]
-impl<RES: Resource> From<Proof> for ProofOf<RES> {
+impl<RES: ResourceDecl> From<Proof> for ProofOf<RES> {
     #[inline(always)]
-    fn from(_inner: Proof) -> Self {
-        panic!("Unsafe creation of ProofOf from Proof.  Enable scrypto_statictypes/runtime_typechecks or use .unchecked_into()");
-        // UncheckedIntoProofOf::unchecked_into(inner)
+    fn from(proof: Proof) -> Self {
+        proof.try_into_of().unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -147,8 +186,8 @@ impl<RES: Resource> ProofOf<RES> {
 
     /// Returns the resource amount within the bucket.
     #[inline(always)]
-    pub fn amount(&self) -> Decimal {
-        self.with_inner(|inner| inner.amount())
+    pub fn amount(&self) -> AmountOf<RES> {
+        self.with_inner(|inner| inner.amount()).unchecked_into()
     }
 
     /// Returns the resource address.
@@ -157,6 +196,21 @@ impl<RES: Resource> ProofOf<RES> {
         self.with_inner(|inner| inner.resource_address())
     }
 
+    /// Destroys this proof.
+    #[inline(always)]
+    pub fn drop(self) {
+        self.unwrap().drop()
+    }
+
+    /// Checks if the referenced bucket is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.with_inner(|inner| inner.is_empty())
+    }
+}
+
+// non-fungible only forwarding implementations
+impl<RES: NonFungibleResource> ProofOf<RES> {
     /// Returns the ids of all non-fungibles in this bucket.
     ///
     /// # Panics
@@ -168,32 +222,91 @@ impl<RES: Resource> ProofOf<RES> {
 
     /// Returns all the non-fungible units contained.
     ///
+    /// When the data type is declared with `declare_resource!(NAME, non_fungible = Data)` any other `T` will not compile.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// #[derive(NonFungibleData)]
+    /// pub struct MemberData {
+    ///     name: String,
+    /// }
+    ///
+    /// declare_resource!(MEMBER, non_fungible = MemberData);
+    ///
+    /// fn members(proof: &ProofOf<MEMBER>) -> Vec<NonFungible<MemberData>> {
+    ///     proof.non_fungibles()
+    /// }
+    /// ```
+    ///
+    /// ```compile_fail,E0277
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// #[derive(NonFungibleData)]
+    /// pub struct MemberData {
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(NonFungibleData)]
+    /// pub struct TicketData {
+    ///     seat: u32,
+    /// }
+    ///
+    /// declare_resource!(MEMBER, non_fungible = MemberData);
+    ///
+    /// fn tickets(proof: &ProofOf<MEMBER>) -> Vec<NonFungible<TicketData>> {
+    ///     proof.non_fungibles()
+    /// }
+    /// ```
+    ///
     /// # Panics
     /// Panics if this is not a non-fungible proof.
     #[inline(always)]
-    pub fn non_fungibles<T: NonFungibleData>(&self) -> Vec<NonFungible<T>> {
+    pub fn non_fungibles<T: NonFungibleData>(&self) -> Vec<NonFungible<T>>
+    where RES::Kind: AcceptsNonFungibleData<T> {
         self.with_inner(|inner| inner.non_fungibles())
     }
 
     /// Returns a singleton non-fungible.
     ///
+    /// When the data type is declared with `declare_resource!(NAME, non_fungible = Data)` any other `T` will not compile.
+    ///
     /// # Panics
     /// Panics if this is not a singleton proof
     #[inline(always)]
-    pub fn non_fungible<T: NonFungibleData>(&self) -> NonFungible<T> {
+    pub fn non_fungible<T: NonFungibleData>(&self) -> NonFungible<T>
+    where RES::Kind: AcceptsNonFungibleData<T> {
         self.with_inner(|inner| inner.non_fungible())
     }
+}
 
-    /// Destroys this proof.
+// declared non-fungible data type implementations
+impl<RES: NonFungibleDataResource> ProofOf<RES> {
+    /// Returns the data of all the non-fungible units contained, using the declared data type.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible proof.
     #[inline(always)]
-    pub fn drop(self) {
-        self.unwrap().drop()
+    pub fn non_fungible_data(&self) -> BTreeMap<NonFungibleId, RES::Data> {
+        self.with_inner(|inner| inner.non_fungibles::<RES::Data>())
+            .into_iter()
+            .map(|non_fungible| (non_fungible.id(), non_fungible.data()))
+            .collect()
     }
 
-    /// Checks if the referenced bucket is empty.
+    /// Updates the data of a non-fungible, using the declared data type.
+    ///
+    /// # Panics
+    /// Panics if the non-fungible does not exist or the update is not authorized.
     #[inline(always)]
-    pub fn is_empty(&self) -> bool {
-        self.with_inner(|inner| inner.is_empty())
+    pub fn update_non_fungible_data(&self, non_fungible_id: &NonFungibleId, data: RES::Data) {
+        self.resource_manager().update_non_fungible_data(non_fungible_id, data)
     }
 }
 
@@ -0,0 +1,136 @@
+//! Inspect and control the runtime bindings of resources declared without an address
+//!
+//! With `runtime_typechecks` the first address seen for such a resource (or with `trusted_bindings` the first one from a
+//! trusted source) is remembered and all later conversions must match it.  These functions make that explicit:
+//! bind deliberately at instantiation with `MYTOKEN::bind(address)`, and debug binding problems with `dump()`.
+//!
+//! Without `runtime_typechecks` nothing is remembered, only declared addresses are reported.
+
+use scrypto::prelude::ResourceAddress;
+
+use crate::error::StaticTypeError;
+use crate::internal::*;
+use crate::resourceof::ResourceOf;
+
+#[cfg(feature = "runtime_typechecks")]
+use crate::runtime::runtimechecks;
+
+/// Explicit binding of a declared resource, ie. `MYTOKEN::bind(address)`
+pub trait ResourceBinding: Resource + Sized {
+    /// Binds this resource to an address (a trusted source with `trusted_bindings`), same as `ResourceOf::bind`.
+    fn bind(resource_address: ResourceAddress) -> Result<ResourceOf<Self>, StaticTypeError>;
+
+    /// Whether this resource is declared with, or bound to, an address.
+    fn is_bound() -> bool {
+        Self::bound_address().is_some()
+    }
+
+    /// Returns the declared or bound address of this resource.
+    fn bound_address() -> Option<ResourceAddress>;
+}
+
+#[cfg(feature = "runtime_typechecks")]
+impl<RES: runtimechecks::Resource> ResourceBinding for RES {
+    #[inline(always)]
+    fn bind(resource_address: ResourceAddress) -> Result<ResourceOf<Self>, StaticTypeError> {
+        ResourceOf::<RES>::bind(resource_address)
+    }
+
+    #[inline(always)]
+    fn bound_address() -> Option<ResourceAddress> {
+        runtimechecks::bound_address::<RES>()
+    }
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: ResourceDecl> ResourceBinding for RES {
+    #[inline(always)]
+    fn bind(resource_address: ResourceAddress) -> Result<ResourceOf<Self>, StaticTypeError> {
+        ResourceOf::<RES>::bind(resource_address)
+    }
+
+    #[inline(always)]
+    fn bound_address() -> Option<ResourceAddress> {
+        RES::ADDRESS
+    }
+}
+
+/// Whether `RES` is declared with, or bound to, an address.
+#[inline(always)]
+pub fn is_bound<RES: ResourceBinding>() -> bool {
+    RES::is_bound()
+}
+
+/// Returns the declared or bound address of `RES`.
+#[inline(always)]
+pub fn bound_address<RES: ResourceBinding>() -> Option<ResourceAddress> {
+    RES::bound_address()
+}
+
+/// Removes the runtime binding of `RES` (if any), returning the address it was bound to.
+#[cfg(feature = "runtime_typechecks")]
+#[inline(always)]
+pub fn unbind<RES: runtimechecks::Resource>() -> Option<ResourceAddress> {
+    runtimechecks::unbind::<RES>()
+}
+
+/// Removes the runtime binding of `RES` (if any), returning the address it was bound to.
+#[cfg(not(feature = "runtime_typechecks"))]
+#[inline(always)]
+pub fn unbind<RES: ResourceDecl>() -> Option<ResourceAddress> {
+    None
+}
+
+/// Returns the failed checks of resources with `FailurePolicy::Count`, in all scopes (only the first 16 are kept).
+#[cfg(feature = "runtime_typechecks")]
+#[inline(always)]
+pub fn mismatches() -> Vec<StaticTypeError> {
+    runtimechecks::mismatches()
+}
+
+/// Returns the failed checks of resources with `FailurePolicy::Count`, in all scopes (only the first 16 are kept).
+#[cfg(not(feature = "runtime_typechecks"))]
+#[inline(always)]
+pub fn mismatches() -> Vec<StaticTypeError> {
+    Vec::new()
+}
+
+/// Returns the number of failed checks of resources with `FailurePolicy::Count`, including those not kept.
+#[cfg(feature = "runtime_typechecks")]
+#[inline(always)]
+pub fn mismatch_count() -> usize {
+    runtimechecks::mismatch_count()
+}
+
+/// Returns the number of failed checks of resources with `FailurePolicy::Count`, including those not kept.
+#[cfg(not(feature = "runtime_typechecks"))]
+#[inline(always)]
+pub fn mismatch_count() -> usize {
+    0
+}
+
+/// Removes all runtime bindings and recorded mismatches, ie. between tests.
+#[cfg(feature = "runtime_typechecks")]
+#[inline(always)]
+pub fn reset() {
+    runtimechecks::reset()
+}
+
+/// Removes all runtime bindings and recorded mismatches, ie. between tests.
+#[cfg(not(feature = "runtime_typechecks"))]
+#[inline(always)]
+pub fn reset() {}
+
+/// Lists all runtime bindings as `(type_name, address)`, sorted by type name (declared addresses are not included).
+#[cfg(feature = "runtime_typechecks")]
+#[inline(always)]
+pub fn dump() -> Vec<(&'static str, ResourceAddress)> {
+    runtimechecks::dump()
+}
+
+/// Lists all runtime bindings as `(type_name, address)`, sorted by type name (declared addresses are not included).
+#[cfg(not(feature = "runtime_typechecks"))]
+#[inline(always)]
+pub fn dump() -> Vec<(&'static str, ResourceAddress)> {
+    Vec::new()
+}
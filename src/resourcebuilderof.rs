@@ -0,0 +1,255 @@
+use std::marker::PhantomData;
+
+use scrypto::prelude::*;
+
+use crate::bucketof::BucketOf;
+use crate::internal::*;
+use crate::resourceof::ResourceOf;
+
+#[cfg(feature = "runtime_typechecks")]
+use crate::runtime::runtimechecks;
+
+/// Typed version of `ResourceBuilder` for creating a declared resource
+///
+/// `ResourceBuilderOf::<MYTOKEN>::new_fungible()` and `ResourceBuilderOf::<BADGE>::new_non_fungible()` mirror
+/// `ResourceBuilder` but return a `BucketOf<RES>` from `initial_supply` and a `ResourceOf<RES>` from
+/// `no_initial_supply`.  With `runtime_typechecks` the new address becomes the binding for `RES`.
+pub struct ResourceBuilderOf<RES> {
+    phantom: PhantomData<RES>,
+}
+
+impl<RES: FungibleResource + 'static> ResourceBuilderOf<RES> {
+    /// Starts a new builder to create fungible resource, with the declared divisibility and metadata.
+    #[inline(always)]
+    pub fn new_fungible() -> FungibleResourceBuilderOf<RES> {
+        let mut builder = FungibleResourceBuilderOf::<RES> {
+            inner: ResourceBuilder::new_fungible(),
+            phantom: PhantomData,
+        };
+        if let Some(divisibility) = RES::DIVISIBILITY {
+            builder = builder.divisibility(divisibility);
+        }
+        for (name, value) in declared_metadata::<RES>() {
+            builder = builder.metadata(name, value);
+        }
+        builder
+    }
+}
+
+impl<RES: NonFungibleResource + 'static> ResourceBuilderOf<RES> {
+    /// Starts a new builder to create non-fungible resource, with the declared metadata.
+    #[inline(always)]
+    pub fn new_non_fungible() -> NonFungibleResourceBuilderOf<RES> {
+        let mut builder = NonFungibleResourceBuilderOf::<RES> {
+            inner: ResourceBuilder::new_non_fungible(),
+            phantom: PhantomData,
+        };
+        for (name, value) in declared_metadata::<RES>() {
+            builder = builder.metadata(name, value);
+        }
+        builder
+    }
+}
+
+// all metadata declared with declare_resource!, including name and symbol
+pub(crate) fn declared_metadata<RES: ResourceDecl>() -> impl Iterator<Item = (&'static str, &'static str)> {
+    RES::NAME
+        .map(|name| ("name", name))
+        .into_iter()
+        .chain(RES::SYMBOL.map(|symbol| ("symbol", symbol)))
+        .chain(RES::METADATA.iter().copied())
+}
+
+// bind the address of a newly created resource to RES
+#[cfg(feature = "runtime_typechecks")]
+#[inline(always)]
+fn bind_created<RES: runtimechecks::Resource>(resource_address: ResourceAddress) -> ResourceAddress {
+    if let Err(e) = runtimechecks::bind::<RES>(resource_address, runtimechecks::BindingSource::Builder) {
+        panic!("ResourceBuilderOf mismatch: {}", e);
+    }
+    resource_address
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+#[inline(always)]
+fn bind_created<RES: ResourceDecl>(resource_address: ResourceAddress) -> ResourceAddress {
+    resource_address
+}
+
+#[inline(always)]
+fn require_badge<BADGE: Resource>(badge: &ResourceOf<BADGE>) -> AccessRule {
+    rule!(require(badge._resource_address()))
+}
+
+/// Typed version of `FungibleResourceBuilder`
+pub struct FungibleResourceBuilderOf<RES> {
+    inner: FungibleResourceBuilder,
+    phantom: PhantomData<RES>,
+}
+
+impl<RES: FungibleResource + 'static> FungibleResourceBuilderOf<RES> {
+    /// Sets the divisibility.
+    #[inline(always)]
+    pub fn divisibility(mut self, divisibility: u8) -> Self {
+        self.inner = self.inner.divisibility(divisibility);
+        self
+    }
+
+    /// Adds a resource metadata.
+    #[inline(always)]
+    pub fn metadata<K: AsRef<str>, V: AsRef<str>>(mut self, name: K, value: V) -> Self {
+        self.inner = self.inner.metadata(name, value);
+        self
+    }
+
+    /// Sets the mint rule.
+    #[inline(always)]
+    pub fn mintable(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.mintable(method_auth, mutability);
+        self
+    }
+
+    /// Sets the mint rule to require a typed badge.
+    #[inline(always)]
+    pub fn mintable_by<BADGE: Resource>(self, badge: &ResourceOf<BADGE>, mutability: Mutability) -> Self {
+        self.mintable(require_badge(badge), mutability)
+    }
+
+    /// Sets the burn rule.
+    #[inline(always)]
+    pub fn burnable(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.burnable(method_auth, mutability);
+        self
+    }
+
+    /// Sets the burn rule to require a typed badge.
+    #[inline(always)]
+    pub fn burnable_by<BADGE: Resource>(self, badge: &ResourceOf<BADGE>, mutability: Mutability) -> Self {
+        self.burnable(require_badge(badge), mutability)
+    }
+
+    /// Sets the withdraw rule.
+    #[inline(always)]
+    pub fn restrict_withdraw(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.restrict_withdraw(method_auth, mutability);
+        self
+    }
+
+    /// Sets the deposit rule.
+    #[inline(always)]
+    pub fn restrict_deposit(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.restrict_deposit(method_auth, mutability);
+        self
+    }
+
+    /// Sets the update metadata rule.
+    #[inline(always)]
+    pub fn updateable_metadata(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.updateable_metadata(method_auth, mutability);
+        self
+    }
+
+    /// Creates resource with the given initial supply.
+    #[inline(always)]
+    pub fn initial_supply<T: Into<Decimal>>(self, amount: T) -> BucketOf<RES> {
+        let bucket = self.inner.initial_supply(amount);
+        bind_created::<RES>(bucket.resource_address());
+        bucket.unchecked_into()
+    }
+
+    /// Creates resource with no initial supply.
+    #[inline(always)]
+    pub fn no_initial_supply(self) -> ResourceOf<RES> {
+        bind_created::<RES>(self.inner.no_initial_supply()).unchecked_into()
+    }
+}
+
+/// Typed version of `NonFungibleResourceBuilder`
+pub struct NonFungibleResourceBuilderOf<RES> {
+    inner: NonFungibleResourceBuilder,
+    phantom: PhantomData<RES>,
+}
+
+impl<RES: NonFungibleResource + 'static> NonFungibleResourceBuilderOf<RES> {
+    /// Adds a resource metadata.
+    #[inline(always)]
+    pub fn metadata<K: AsRef<str>, V: AsRef<str>>(mut self, name: K, value: V) -> Self {
+        self.inner = self.inner.metadata(name, value);
+        self
+    }
+
+    /// Sets the mint rule.
+    #[inline(always)]
+    pub fn mintable(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.mintable(method_auth, mutability);
+        self
+    }
+
+    /// Sets the mint rule to require a typed badge.
+    #[inline(always)]
+    pub fn mintable_by<BADGE: Resource>(self, badge: &ResourceOf<BADGE>, mutability: Mutability) -> Self {
+        self.mintable(require_badge(badge), mutability)
+    }
+
+    /// Sets the burn rule.
+    #[inline(always)]
+    pub fn burnable(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.burnable(method_auth, mutability);
+        self
+    }
+
+    /// Sets the burn rule to require a typed badge.
+    #[inline(always)]
+    pub fn burnable_by<BADGE: Resource>(self, badge: &ResourceOf<BADGE>, mutability: Mutability) -> Self {
+        self.burnable(require_badge(badge), mutability)
+    }
+
+    /// Sets the withdraw rule.
+    #[inline(always)]
+    pub fn restrict_withdraw(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.restrict_withdraw(method_auth, mutability);
+        self
+    }
+
+    /// Sets the deposit rule.
+    #[inline(always)]
+    pub fn restrict_deposit(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.restrict_deposit(method_auth, mutability);
+        self
+    }
+
+    /// Sets the update metadata rule.
+    #[inline(always)]
+    pub fn updateable_metadata(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.updateable_metadata(method_auth, mutability);
+        self
+    }
+
+    /// Sets the update non-fungible data rule.
+    #[inline(always)]
+    pub fn updateable_non_fungible_data(mut self, method_auth: AccessRule, mutability: Mutability) -> Self {
+        self.inner = self.inner.updateable_non_fungible_data(method_auth, mutability);
+        self
+    }
+
+    /// Creates resource with the given initial supply.
+    ///
+    /// When the data type is declared with `declare_resource!(NAME, non_fungible = Data)` any other `V` will not compile.
+    #[inline(always)]
+    pub fn initial_supply<T, V>(self, entries: T) -> BucketOf<RES>
+    where
+        T: IntoIterator<Item = (NonFungibleId, V)>,
+        V: NonFungibleData,
+        RES::Kind: AcceptsNonFungibleData<V>,
+    {
+        let bucket = self.inner.initial_supply(entries);
+        bind_created::<RES>(bucket.resource_address());
+        bucket.unchecked_into()
+    }
+
+    /// Creates resource with no initial supply.
+    #[inline(always)]
+    pub fn no_initial_supply(self) -> ResourceOf<RES> {
+        bind_created::<RES>(self.inner.no_initial_supply()).unchecked_into()
+    }
+}
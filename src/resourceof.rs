@@ -1,13 +1,14 @@
 use scrypto::prelude::*;
 
+use crate::amountof::*;
 use crate::bucketof::BucketOf;
 use crate::internal::*;
 
 #[cfg(feature = "runtime_typechecks")]
 use crate::runtime::runtimechecks;
 
-impl_wrapper_struct!(ResourceOf<RES>, ResourceAddress, noderef); // use custom deref to borrowed ResourceManager instead of default
-impl_SBOR_traits!(ResourceOf<RES>, ResourceAddress);
+impl_wrapper_struct!(ResourceOf<RES>, ResourceAddress, noderef); // no deref (to the ResourceManager), so the methods of the wrong kind can't be reached
+impl_SBOR_traits!(ResourceOf<RES>, ResourceAddress, resource_of(Decoded)); // from component state or an argument
 impl SBORable for ResourceAddress {}
 impl Container for ResourceAddress {}
 
@@ -17,39 +18,153 @@ impl<RES: Resource> HasResourceAddress for ResourceOf<RES> {
     }
 }
 
-impl<RES: Resource> ResourceOf<RES> {
+impl<RES: FungibleResource> ResourceOf<RES> {
     /// Mints fungible resources
+    ///
+    /// Only for fungible (or undeclared kind) resources, for a `non_fungible` one it doesn't compile:
+    /// ```compile_fail,E0599
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// declare_resource!(BADGE, non_fungible);
+    ///
+    /// fn mint(badge: &ResourceOf<BADGE>) {
+    ///     let _ = badge.mint(1);
+    /// }
+    /// ```
     #[inline(always)]
-    pub fn mint<T: Into<Decimal>>(&self, amount: T) -> BucketOf<RES> {
-        self.borrow_resource_manager().mint(amount).unchecked_into()
+    pub fn mint<T: IntoAmountOf<RES>>(&self, amount: T) -> BucketOf<RES> {
+        self.borrow_resource_manager().mint(amount.into_amount_of().unwrap()).unchecked_into()
     }
+}
 
+impl<RES: NonFungibleResource> ResourceOf<RES> {
     /// Mints non-fungible resources
+    ///
+    /// Only for non-fungible (or undeclared kind) resources, for a `fungible` one it doesn't compile:
+    /// ```compile_fail,E0599
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// #[derive(NonFungibleData)]
+    /// pub struct MemberData {
+    ///     name: String,
+    /// }
+    ///
+    /// declare_resource!(MYTOKEN, fungible);
+    ///
+    /// fn mint(token: &ResourceOf<MYTOKEN>, id: &NonFungibleId, data: MemberData) {
+    ///     let _ = token.mint_non_fungible(id, data);
+    /// }
+    /// ```
+    ///
+    /// When the data type is declared with `declare_resource!(NAME, non_fungible = Data)` any other `T` will not compile.
     #[inline(always)]
-    pub fn mint_non_fungible<T: NonFungibleData>(&self, id: &NonFungibleId, data: T) -> BucketOf<RES> {
+    pub fn mint_non_fungible<T: NonFungibleData>(&self, id: &NonFungibleId, data: T) -> BucketOf<RES>
+    where RES::Kind: AcceptsNonFungibleData<T> {
         self.borrow_resource_manager()
             .mint_non_fungible(id, data)
             .unchecked_into()
     }
+}
+
+impl<RES: NonFungibleDataResource> ResourceOf<RES> {
+    /// Returns the data of a non-fungible, using the declared data type.
+    ///
+    /// # Panics
+    /// Panics if the non-fungible does not exist.
+    #[inline(always)]
+    pub fn non_fungible_data(&self, id: &NonFungibleId) -> RES::Data {
+        self.borrow_resource_manager().get_non_fungible_data(id)
+    }
+
+    /// Updates the data of a non-fungible, using the declared data type.
+    ///
+    /// # Panics
+    /// Panics if the non-fungible does not exist or the update is not authorized.
+    #[inline(always)]
+    pub fn update_non_fungible_data(&self, id: &NonFungibleId, data: RES::Data) {
+        self.borrow_resource_manager().update_non_fungible_data(id, data)
+    }
+}
+
+#[cfg(feature = "runtime_typechecks")]
+impl<RES: runtimechecks::Resource> ResourceOf<RES> {
+    /// Explicitly binds `RES` to an address from a trusted source, ie. a `ResourceAddress` stored in component state.
+    ///
+    /// With `trusted_bindings` this (along with `VaultOf` in component state and `ResourceBuilderOf`) is the only way an
+    /// address can be bound to a resource declared without an address.
+    #[inline(always)]
+    pub fn bind(resource_address: ResourceAddress) -> Result<Self, StaticTypeError> {
+        runtimechecks::bind::<RES>(resource_address, runtimechecks::BindingSource::Explicit)?;
+        Ok(resource_address.unchecked_into())
+    }
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: ResourceDecl> ResourceOf<RES> {
+    /// Explicitly binds `RES` to an address from a trusted source, ie. a `ResourceAddress` stored in component state.
+    ///
+    /// Without `runtime_typechecks` there is nothing to bind, and this never fails.
+    #[inline(always)]
+    pub fn bind(resource_address: ResourceAddress) -> Result<Self, StaticTypeError> {
+        Ok(resource_address.unchecked_into())
+    }
+}
+
+impl<RES: Resource> ResourceOf<RES> {
+    /// Returns the total supply of this resource.
+    #[inline(always)]
+    pub fn total_supply(&self) -> AmountOf<RES> {
+        self.borrow_resource_manager().total_supply().unchecked_into()
+    }
 
     /// Burns a bucket of resources.
     #[inline(always)]
     pub fn burn(&self, bucket: BucketOf<RES>) {
         self.borrow_resource_manager().burn(bucket.inner)
     }
+
+    /// Returns the resource address.
+    #[inline(always)]
+    pub fn resource_address(&self) -> ResourceAddress {
+        self.inner
+    }
+
+    /// Returns the resource type.
+    #[inline(always)]
+    pub fn resource_type(&self) -> ResourceType {
+        self.borrow_resource_manager().resource_type()
+    }
+
+    /// Returns the metadata associated with this resource.
+    #[inline(always)]
+    pub fn metadata(&self) -> HashMap<String, String> {
+        self.borrow_resource_manager().metadata()
+    }
 }
 
-// custom impl Deref to borrowed ResourceManager
-impl<RES: Resource> std::ops::Deref for ResourceOf<RES> {
-    type Target = ResourceManager;
+impl<RES: NonFungibleResource> ResourceOf<RES> {
+    /// Checks if a non-fungible exists.
+    #[inline(always)]
+    pub fn non_fungible_exists(&self, id: &NonFungibleId) -> bool {
+        self.borrow_resource_manager().non_fungible_exists(id)
+    }
+}
 
+// the untyped ResourceManager, ie. to change its access rules (its mint methods are not kind gated)
+impl<RES: Resource> AsRef<ResourceManager> for ResourceOf<RES> {
     #[inline(always)]
-    fn deref(&self) -> &Self::Target {
+    fn as_ref(&self) -> &ResourceManager {
         self.borrow_resource_manager()
     }
 }
 
-// because of the custom deref we need a specific WithInner implementation too
+// WithInner is the address, not the borrowed ResourceManager of as_ref()
 impl<RES: Resource> WithInner<ResourceAddress> for ResourceOf<RES> {
     type Inner = ResourceAddress;
     #[inline(always)]
@@ -58,14 +173,48 @@ impl<RES: Resource> WithInner<ResourceAddress> for ResourceOf<RES> {
     }
 }
 
+#[cfg(feature = "runtime_typechecks")]
+impl<RES: runtimechecks::Resource> TryIntoOf<RES, ResourceOf<RES>> for ResourceAddress {
+    #[inline(always)]
+    fn try_into_of(self) -> Result<ResourceOf<RES>, ConversionError<Self>> {
+        resource_of(self, runtimechecks::BindingSource::Untrusted)
+    }
+}
+
+#[cfg(feature = "runtime_typechecks")]
+fn resource_of<RES: runtimechecks::Resource>(
+    resource_address: ResourceAddress,
+    source: runtimechecks::BindingSource,
+) -> Result<ResourceOf<RES>, ConversionError<ResourceAddress>> {
+    match runtimechecks::check_address_from::<RES>(resource_address, source) {
+        Ok(()) => Ok(resource_address.unchecked_into()),
+        Err(error) => Err(ConversionError { value: resource_address, error }),
+    }
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: ResourceDecl> TryIntoOf<RES, ResourceOf<RES>> for ResourceAddress {
+    #[inline(always)]
+    fn try_into_of(self) -> Result<ResourceOf<RES>, ConversionError<Self>> {
+        match check_declared_address::<RES>(self) {
+            Ok(()) => Ok(self.unchecked_into()),
+            Err(error) => Err(ConversionError { value: self, error }),
+        }
+    }
+}
+
 #[cfg(feature = "runtime_typechecks")]
 impl<RES: runtimechecks::Resource> From<ResourceAddress> for ResourceOf<RES> {
     fn from(resource_address: ResourceAddress) -> Self {
-        if !runtimechecks::check_address::<RES>(resource_address) {
-            // not sure a better error here as with BucketOf and VaultOf
-            panic!("ResourceOf mismatch");
-        }
-        resource_address.unchecked_into()
+        resource_address.try_into_of().unwrap_or_else(|e| panic!("ResourceOf mismatch: {}", e))
+    }
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: ResourceDecl> From<ResourceAddress> for ResourceOf<RES> {
+    #[inline(always)]
+    fn from(resource_address: ResourceAddress) -> Self {
+        resource_address.try_into_of().unwrap_or_else(|e| panic!("ResourceOf mismatch: {}", e))
     }
 }
 
@@ -1,14 +1,18 @@
 #[cfg(feature = "runtime_typechecks")]
 pub(crate) mod runtimechecks {
-    use scrypto::prelude::{debug, error};
+    use scrypto::prelude::{debug, error, ComponentAddress, Runtime, ScryptoActor};
 
+    use crate::error::StaticTypeError;
     use crate::internal::*;
+    use crate::resourcebuilderof::declared_metadata;
 
     type AddressKey = std::any::TypeId;
 
     pub trait Resource: crate::internal::Resource {
         fn index() -> AddressKey;
         fn address() -> Option<ResourceAddress>;
+        fn check_declaration(address: ResourceAddress) -> Result<(), StaticTypeError>;
+        fn failure_policy() -> FailurePolicy;
     }
     impl<T: ResourceDecl + 'static> Resource for T {
         #[inline(always)]
@@ -20,119 +24,418 @@ pub(crate) mod runtimechecks {
         fn address() -> Option<ResourceAddress> {
             T::ADDRESS
         }
+
+        #[inline(always)]
+        fn check_declaration(address: ResourceAddress) -> Result<(), StaticTypeError> {
+            check_declaration::<T>(address)
+        }
+
+        #[inline(always)]
+        fn failure_policy() -> FailurePolicy {
+            T::FAILURE_POLICY.unwrap_or(DEFAULT_FAILURE_POLICY)
+        }
     }
 
-    // Adapted from https://stackoverflow.com/questions/27791532/how-do-i-create-a-global-mutable-singleton
-    // replace this with only_once when it's in rustc standard
+    // ensure the ledger resource matches the declared kind, metadata and divisibility
+    fn check_declaration<T: ResourceDecl>(address: ResourceAddress) -> Result<(), StaticTypeError> {
+        use scrypto::prelude::*; // make sure the macro works
+        let kind = <T::Kind as ResourceKind>::NAME;
+        if kind.is_none() && T::NAME.is_none() && T::SYMBOL.is_none() && T::DIVISIBILITY.is_none() && T::METADATA.is_empty() {
+            return Ok(()); // nothing declared, skip the ledger lookups
+        }
+        let resource = std::any::type_name::<T>();
+        let resource_manager = borrow_resource_manager!(address);
+        let (actual_kind, actual_divisibility) = match resource_manager.resource_type() {
+            ResourceType::Fungible { divisibility } => ("fungible", Some(divisibility)),
+            ResourceType::NonFungible => ("non_fungible", None),
+        };
+        if let Some(expected) = kind {
+            if expected != actual_kind {
+                error!(
+                    "check_declaration kind mismatch {}: {}: {} != {}",
+                    resource,
+                    address,
+                    actual_kind,
+                    expected
+                );
+                return Err(StaticTypeError::KindMismatch {
+                    resource,
+                    address,
+                    expected,
+                    actual: actual_kind,
+                });
+            }
+        }
+        let metadata = resource_manager.metadata();
+        for (name, expected) in declared_metadata::<T>() {
+            let actual = metadata.get(name).map(|value| value.as_str());
+            if actual != Some(expected) {
+                error!(
+                    "check_declaration metadata mismatch {}: {}: {} {:?} != {:?}",
+                    resource,
+                    address,
+                    name,
+                    actual,
+                    expected
+                );
+                return Err(StaticTypeError::DeclarationMismatch {
+                    resource,
+                    address,
+                    field: name,
+                });
+            }
+        }
+        if let Some(expected) = T::DIVISIBILITY {
+            if actual_divisibility != Some(expected) {
+                error!(
+                    "check_declaration divisibility mismatch {}: {}: {:?} != {}",
+                    resource,
+                    address,
+                    actual_divisibility,
+                    expected
+                );
+                return Err(StaticTypeError::DeclarationMismatch {
+                    resource,
+                    address,
+                    field: "divisibility",
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // ensure the component is of the declared blueprint (only if its package or name is declared)
+    pub fn check_blueprint<B: BlueprintDecl>(component_address: ComponentAddress) -> Result<(), StaticTypeError> {
+        use scrypto::prelude::*; // make sure the macro works
+        if B::PACKAGE.is_none() && B::NAME.is_none() {
+            return Ok(()); // nothing declared, skip the ledger lookup
+        }
+        let blueprint = std::any::type_name::<B>();
+        let component = borrow_component!(component_address);
+        if let Some(expected) = B::PACKAGE {
+            let actual = component.package_address();
+            if actual != expected {
+                error!("check_blueprint package mismatch {}: {}: {} != {}", blueprint, component_address, actual, expected);
+                return Err(StaticTypeError::BlueprintMismatch {
+                    blueprint,
+                    component: component_address,
+                    field: "package",
+                });
+            }
+        }
+        if let Some(expected) = B::NAME {
+            let actual = component.blueprint_name();
+            if actual != expected {
+                error!("check_blueprint name mismatch {}: {}: {} != {}", blueprint, component_address, actual, expected);
+                return Err(StaticTypeError::BlueprintMismatch {
+                    blueprint,
+                    component: component_address,
+                    field: "blueprint name",
+                });
+            }
+        }
+        debug!("check_blueprint matched: {}: {}", blueprint, component_address);
+        Ok(())
+    }
 
-    use std::mem::MaybeUninit;
-    use std::sync::{Mutex, Once};
+    // a blueprint only uses a handful of resources, so a small fixed capacity keeps the registry allocation-free
+    // (and a linear scan is cheaper than hashing at this size)
+    const MAX_BINDINGS: usize = 32;
+    const MAX_MISMATCHES: usize = 16;
 
-    #[derive(Default)]
-    struct KnownAddresses {
-        addresses: std::collections::HashMap<AddressKey, ResourceAddress>,
-        all_addresses: std::collections::HashSet<ResourceAddress>,
+    // bindings are scoped to the component instance being called, so components of the same blueprint (each with their
+    // own resources) can be called in one process, or to the blueprint function call (ie. instantiation) which made them
+    // the Radix Engine creates a WASM instance (and so a registry) for each call, so a function scope is never shared
+    // between calls (ie. two instantiations with new), unlike the scope of a component
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum ScopeKey {
+        Declared,
+        Function,
+        Component(ComponentAddress),
     }
 
-    struct SingletonReader {
-        // Since we will be used in many threads, we need to protect
-        // concurrent access
-        inner: Mutex<KnownAddresses>,
+    #[derive(Clone, Copy)]
+    struct Binding {
+        scope: ScopeKey, // always Declared for declared addresses, which are the same in every scope
+        index: AddressKey,
+        address: ResourceAddress,
+        name: &'static str, // type name, for errors and dump()
+        declared: bool,     // seen declared address, only kept to detect other resources using it
     }
 
-    fn singleton() -> &'static SingletonReader {
-        // Create an uninitialized static
-        static mut SINGLETON: MaybeUninit<SingletonReader> = MaybeUninit::uninit();
-        static ONCE: Once = Once::new();
+    struct Registry {
+        bindings: [Option<Binding>; MAX_BINDINGS],
+        mismatches: [Option<StaticTypeError>; MAX_MISMATCHES], // failures of resources with FailurePolicy::Count, in all scopes
+        mismatch_count: usize,                                 // including those which didn't fit
+        scope: Option<ScopeKey>,                               // of the call frame, from Runtime::actor() on first use
+    }
 
-        unsafe {
-            ONCE.call_once(|| {
-                // Make it
-                let singleton = SingletonReader {
-                    inner: Mutex::new(KnownAddresses::default()),
-                };
-                // Store it to the static var, i.e. initialize it
-                SINGLETON.write(singleton);
-            });
+    impl Registry {
+        const fn new() -> Self {
+            const NO_BINDING: Option<Binding> = None;
+            const NO_MISMATCH: Option<StaticTypeError> = None;
+            Registry {
+                bindings: [NO_BINDING; MAX_BINDINGS],
+                mismatches: [NO_MISMATCH; MAX_MISMATCHES],
+                mismatch_count: 0,
+                scope: None,
+            }
+        }
+
+        fn find<P: Fn(&Binding) -> bool>(&self, predicate: P) -> Option<&Binding> {
+            self.bindings.iter().flatten().find(|binding| predicate(binding))
+        }
+
+        fn insert(&mut self, binding: Binding) -> bool {
+            match self.bindings.iter_mut().find(|slot| slot.is_none()) {
+                Some(slot) => {
+                    *slot = Some(binding);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        fn remove<P: Fn(&Binding) -> bool>(&mut self, predicate: P) -> Option<Binding> {
+            let slot = self.bindings.iter_mut().find(|slot| slot.as_ref().map_or(false, &predicate))?;
+            slot.take()
+        }
+
+        fn record_mismatch(&mut self, e: StaticTypeError) {
+            if let Some(slot) = self.mismatches.get_mut(self.mismatch_count) {
+                *slot = Some(e);
+            }
+            self.mismatch_count += 1;
+        }
+    }
+
+    thread_local! {
+        // a WASM instance is single threaded, so this is a plain static without any locking (or unsafe)
+        static REGISTRY: std::cell::RefCell<Registry> = std::cell::RefCell::new(Registry::new());
+    }
+
+    fn with_registry<O, F: FnOnce(&mut Registry) -> O>(f: F) -> O {
+        REGISTRY.with(|registry| f(&mut registry.borrow_mut()))
+    }
 
-            // Now we give out a shared reference to the data, which is safe to use
-            // concurrently.
-            SINGLETON.assume_init_ref()
+    // the actor can't change during a call, so it is only asked for once (outside the registry borrow, as an engine call)
+    fn current_scope() -> ScopeKey {
+        if let Some(scope) = with_registry(|registry| registry.scope) {
+            return scope;
         }
+        let scope = match Runtime::actor() {
+            ScryptoActor::Component(component_address) => ScopeKey::Component(component_address),
+            _ => ScopeKey::Function,
+        };
+        with_registry(|registry| registry.scope = Some(scope));
+        scope
+    }
+
+    /// Where an address being checked comes from
+    ///
+    /// With `trusted_bindings` only the trusted sources may bind an address to a resource without a declared address,
+    /// anything else must match an existing binding.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BindingSource {
+        /// decoded from component state, only a `Vault` (which can't be passed in)
+        State,
+        /// created by `ResourceBuilderOf`
+        Builder,
+        /// an explicit `bind`
+        Explicit,
+        /// a decoded `ResourceAddress`, from component state or a method argument (which can't be told apart)
+        Decoded,
+        /// anything else, ie. a method argument or a returned `Bucket`
+        Untrusted,
     }
-    // end of only_once alternative
 
-    pub fn check_address<RES: Resource>(address: ResourceAddress) -> bool {
+    impl BindingSource {
+        #[inline(always)]
+        fn may_bind(self) -> bool {
+            !cfg!(feature = "trusted_bindings") || !matches!(self, BindingSource::Decoded | BindingSource::Untrusted)
+        }
+    }
+
+    #[inline(always)]
+    pub fn check_address<RES: Resource>(address: ResourceAddress) -> Result<(), StaticTypeError> {
+        check_address_from::<RES>(address, BindingSource::Untrusted)
+    }
+
+    // applies the failure policy of RES
+    pub fn check_address_from<RES: Resource>(address: ResourceAddress, source: BindingSource) -> Result<(), StaticTypeError> {
+        let result = check_address_strict::<RES>(address, source);
+        match (result, RES::failure_policy()) {
+            (Err(e), FailurePolicy::Warn) => {
+                error!("check_addr continuing unchecked (policy warn): {}", e);
+                Ok(())
+            }
+            (Err(e), FailurePolicy::Count) => {
+                error!("check_addr continuing unchecked (policy count): {}", e);
+                with_registry(|registry| registry.record_mismatch(e));
+                Ok(())
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn check_address_strict<RES: Resource>(address: ResourceAddress, source: BindingSource) -> Result<(), StaticTypeError> {
+        let resource = std::any::type_name::<RES>();
         match RES::address() {
             Some(expected) => {
-                let r = expected == address;
-                if r {
-                    debug!(
-                        "check_addr static matched: {}: {}",
-                        std::any::type_name::<RES>(),
-                        address
-                    );
+                if expected == address {
+                    check_declared_unique::<RES>(address)?;
+                    debug!("check_addr static matched: {}: {}", resource, address);
+                    Ok(())
                 } else {
-                    error!(
-                        "check_addr static mismatch {}: {} != {}",
-                        std::any::type_name::<RES>(),
-                        address,
-                        expected
-                    );
+                    error!("check_addr static mismatch {}: {} != {}", resource, address, expected);
+                    Err(StaticTypeError::Mismatch {
+                        resource,
+                        expected,
+                        actual: address,
+                    })
                 }
-                r
             }
             None => {
-                let mut guard = singleton().inner.lock().unwrap();
-                let KnownAddresses {
-                    ref mut addresses,
-                    ref mut all_addresses,
-                } = &mut *guard;
-                let i = RES::index();
-                match addresses.entry(i) {
-                    std::collections::hash_map::Entry::Occupied(o) => {
-                        let expected = *o.get();
-                        let r = expected == address;
-                        if r {
-                            debug!(
-                                "check_addr dynamic matched: {}: {}",
-                                std::any::type_name::<RES>(),
-                                address
-                            );
-                        } else {
-                            error!(
-                                "check_addr dynamic mismatch {}: {} != {}",
-                                std::any::type_name::<RES>(),
-                                address,
-                                expected
-                            );
-                        }
-                        r
-                    }
-                    std::collections::hash_map::Entry::Vacant(v) => {
-                        // enforce that all Resource declarations are unique, helps detect some errors
-                        // ensure that address is not already in the resource map under another name
-                        // use the set of values to check
-                        if all_addresses.contains(&address) {
-                            error!(
-                                "check_addr dynamic cannot create address in use: {}: {}",
-                                std::any::type_name::<RES>(),
-                                address
-                            );
-                            false
-                        } else {
-                            debug!(
-                                "check_addr dynamic created: {}: {}",
-                                std::any::type_name::<RES>(),
-                                address
-                            );
-                            all_addresses.insert(address);
-                            v.insert(address);
-                            true
-                        }
-                    }
+                let scope = current_scope();
+                let index = RES::index();
+                // copied out, so the registry is not borrowed during the engine calls below (logging, check_declaration)
+                let (bound, bound_to) = with_registry(|registry| {
+                    let bound = registry.find(|b| b.scope == scope && b.index == index).map(|b| b.address);
+                    // ensure that address is not already bound under another name
+                    let bound_to = registry.find(|b| (b.declared || b.scope == scope) && b.address == address);
+                    (bound, bound_to.map(|b| b.name))
+                });
+                if let Some(expected) = bound {
+                    return if expected == address {
+                        debug!("check_addr dynamic matched: {}: {}", resource, address);
+                        Ok(())
+                    } else {
+                        error!("check_addr dynamic mismatch {}: {} != {}", resource, address, expected);
+                        Err(StaticTypeError::Mismatch {
+                            resource,
+                            expected,
+                            actual: address,
+                        })
+                    };
+                }
+                // enforce that all Resource declarations are unique, helps detect some errors
+                if let Some(bound_to) = bound_to {
+                    error!("check_addr dynamic cannot create address in use: {}: {}", resource, address);
+                    return Err(StaticTypeError::AddressAlreadyBound {
+                        resource,
+                        address,
+                        bound_to,
+                    });
+                }
+                if source == BindingSource::Decoded && !source.may_bind() {
+                    // a bare address (ie. a ResourceOf field in component state) grants nothing, so it is accepted
+                    // unbound and only checked once the resource is bound from a trusted source
+                    debug!("check_addr dynamic decoded unbound, not binding: {}: {}", resource, address);
+                    return Ok(());
+                }
+                if !source.may_bind() {
+                    error!("check_addr dynamic cannot bind from untrusted source: {}: {}", resource, address);
+                    return Err(StaticTypeError::Unbound { resource, address });
+                }
+                // declared kind and metadata are verified once, on first binding
+                RES::check_declaration(address)?;
+                let binding = Binding {
+                    scope,
+                    index,
+                    address,
+                    name: resource,
+                    declared: false,
+                };
+                if !with_registry(|registry| registry.insert(binding)) {
+                    error!("check_addr dynamic registry full: {}: {}", resource, address);
+                    return Err(StaticTypeError::RegistryFull { resource });
                 }
+                debug!("check_addr dynamic created: {}: {}", resource, address);
+                Ok(())
             }
         }
     }
+
+    // enforce that a declared address is not used by another resource, declared or bound (on first use, in each scope
+    // for bindings), intentional aliases must use declare_resource_alias! so they are the same type
+    // also verifies the declared kind and metadata on first use, the declared binding caches that it was done
+    fn check_declared_unique<RES: Resource>(address: ResourceAddress) -> Result<(), StaticTypeError> {
+        let resource = std::any::type_name::<RES>();
+        let index = RES::index();
+        if with_registry(|registry| registry.find(|b| b.declared && b.index == index).is_some()) {
+            return Ok(()); // already seen, the common case
+        }
+        let scope = current_scope();
+        let bound_to = with_registry(|registry| {
+            registry.find(|b| (b.declared || b.scope == scope) && b.address == address).map(|b| b.name)
+        });
+        if let Some(bound_to) = bound_to {
+            error!("check_addr static address in use: {}: {} by {}", resource, address, bound_to);
+            return Err(StaticTypeError::AddressAlreadyBound {
+                resource,
+                address,
+                bound_to,
+            });
+        }
+        // not cached on failure, so every use of a mismatched declaration fails
+        RES::check_declaration(address)?;
+        let binding = Binding {
+            scope: ScopeKey::Declared,
+            index,
+            address,
+            name: resource,
+            declared: true,
+        };
+        if !with_registry(|registry| registry.insert(binding)) {
+            // don't fail a statically correct conversion, only the duplicate detection (and caching) is lost
+            debug!("check_addr static registry full, not tracking: {}: {}", resource, address);
+        }
+        Ok(())
+    }
+
+    pub fn bound_address<RES: Resource>() -> Option<ResourceAddress> {
+        RES::address().or_else(|| {
+            let (scope, index) = (current_scope(), RES::index());
+            with_registry(|registry| registry.find(|b| b.scope == scope && b.index == index).map(|b| b.address))
+        })
+    }
+
+    pub fn unbind<RES: Resource>() -> Option<ResourceAddress> {
+        let (scope, index) = (current_scope(), RES::index());
+        let binding = with_registry(|registry| registry.remove(|b| !b.declared && b.scope == scope && b.index == index))?;
+        debug!("unbind: {}: {}", binding.name, binding.address);
+        Some(binding.address)
+    }
+
+    // all scopes
+    pub fn reset() {
+        debug!("reset");
+        with_registry(|registry| *registry = Registry::new());
+    }
+
+    pub fn mismatches() -> Vec<StaticTypeError> {
+        with_registry(|registry| registry.mismatches.iter().flatten().cloned().collect())
+    }
+
+    pub fn mismatch_count() -> usize {
+        with_registry(|registry| registry.mismatch_count)
+    }
+
+    pub fn dump() -> Vec<(&'static str, ResourceAddress)> {
+        let scope = current_scope();
+        let mut bindings: Vec<_> = with_registry(|registry| {
+            let bindings = registry.bindings.iter().flatten();
+            bindings.filter(|b| !b.declared && b.scope == scope).map(|b| (b.name, b.address)).collect()
+        });
+        bindings.sort_by_key(|(name, _)| *name);
+        bindings
+    }
+
+    /// Binds RES to an address from a trusted source, like `ResourceBuilderOf` does for the resource it just created
+    ///
+    /// The first binding wins, so binding at creation makes it authoritative for all later checks.
+    pub fn bind<RES: Resource>(address: ResourceAddress, source: BindingSource) -> Result<(), StaticTypeError> {
+        debug!("bind: {}: {} ({:?})", std::any::type_name::<RES>(), address, source);
+        check_address_from::<RES>(address, source)
+    }
 }
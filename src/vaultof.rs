@@ -1,5 +1,6 @@
 use scrypto::prelude::*;
 
+use crate::amountof::*;
 use crate::bucketof::BucketOf;
 use crate::proofof::*;
 use crate::internal::*;
@@ -8,8 +9,9 @@ use crate::resourceof::ResourceOf;
 #[cfg(feature = "runtime_typechecks")]
 use crate::runtime::runtimechecks;
 
-impl_wrapper_struct!(VaultOf<RES>, Vault);
-impl_SBOR_traits!(VaultOf<RES>, Vault);
+impl_wrapper_struct!(VaultOf<RES>, Vault, noderef); // no deref, so the methods of the wrong kind can't be reached
+impl_wrapper_inner!(VaultOf<RES>, Vault);
+impl_SBOR_traits!(VaultOf<RES>, Vault, vault_of(State)); // only a decoded Vault is from component state
 impl SBORable for Vault {}
 impl Container for Vault {}
 impl_HasResourceAddress!(Vault);
@@ -18,6 +20,9 @@ impl_HasResourceAddress!(Vault);
 impl<RES: runtimechecks::Resource> VaultOf<RES> {
     // runtime_checks requires trait bound on runtimechecks::Resource and use of .into() in new() may have runtime_checks (so we need a different impl block)
     /// Creates an empty vault to permanently hold resource of the given definition.
+    ///
+    /// With `trusted_bindings` the address may be anything the caller passed in, so the resource must already be bound
+    /// (ie. created with `ResourceBuilderOf`, or with `ResourceOf::bind`).
     #[inline(always)]
     pub fn new(resource_address: ResourceAddress) -> Self {
         Vault::new(resource_address).into()
@@ -45,11 +50,17 @@ impl<RES: Resource> VaultOf<RES> {
         self.inner.put(other.inner) // no extra check
     }
 
+    /// Returns the amount of resources within this vault.
+    #[inline(always)]
+    pub fn amount(&self) -> AmountOf<RES> {
+        self.inner.amount().unchecked_into()
+    }
+
     /// Takes some amount of resources out of this vault, with typed result.
     #[inline(always)]
-    pub fn take<A: Into<Decimal>>(&mut self, amount: A) -> BucketOf<RES> {
+    pub fn take<A: IntoAmountOf<RES>>(&mut self, amount: A) -> BucketOf<RES> {
         // self.vault.take(amount).into() // extra check
-        self.inner.take(amount).unchecked_into() // no extra check
+        self.inner.take(amount.into_amount_of().unwrap()).unchecked_into() // no extra check
     }
 
     /// Takes all resourced stored in this vault, with typed result.
@@ -59,8 +70,61 @@ impl<RES: Resource> VaultOf<RES> {
         self.inner.take_all().unchecked_into() // no extra check
     }
 
+    /// Creates an ownership proof of this vault.
+    #[inline(always)]
+    pub fn create_proof(&self) -> ProofOf<RES> {
+        // self.inner.create_proof().unchecked_into()
+        UncheckedIntoProofOf::unchecked_into(self.inner.create_proof())
+    }
+
+    /// Creates an ownership proof of this vault, by amount.
+    #[inline(always)]
+    pub fn create_proof_by_amount<A: IntoAmountOf<RES>>(&self, amount: A) -> ProofOf<RES> {
+        UncheckedIntoProofOf::unchecked_into(self.inner.create_proof_by_amount(amount.into_amount_of().unwrap()))
+    }
+
+    /// Returns the resource definition of resources within this vault.
+    #[inline(always)]
+    pub fn resource_manager(&self) -> ResourceOf<RES> {
+        self.inner.resource_address().unchecked_into()
+    }
+
+    /// Returns the resource address.
+    #[inline(always)]
+    pub fn resource_address(&self) -> ResourceAddress {
+        self.inner.resource_address()
+    }
+
+    /// Checks if this vault is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Runs a closure with a proof of this vault in the auth zone.
+    #[inline(always)]
+    pub fn authorize<F: FnOnce() -> O, O>(&self, f: F) -> O {
+        self.inner.authorize(f)
+    }
+}
+
+impl<RES: NonFungibleResource> VaultOf<RES> {
     /// Takes a specific non-fungible from this vault.
     ///
+    /// Only for non-fungible (or undeclared kind) resources, for a `fungible` one it doesn't compile:
+    /// ```compile_fail,E0599
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// declare_resource!(MYTOKEN, fungible);
+    ///
+    /// fn take(vault: &mut VaultOf<MYTOKEN>, id: &NonFungibleId) -> BucketOf<MYTOKEN> {
+    ///     vault.take_non_fungible(id)
+    /// }
+    /// ```
+    ///
     /// # Panics
     /// Panics if this is not a non-fungible vault or the specified non-fungible resource is not found.
     #[inline(always)]
@@ -70,6 +134,20 @@ impl<RES: Resource> VaultOf<RES> {
 
     /// Takes non-fungibles from this vault.
     ///
+    /// Only for non-fungible (or undeclared kind) resources, for a `fungible` one it doesn't compile:
+    /// ```compile_fail,E0599
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// declare_resource!(MYTOKEN, fungible);
+    ///
+    /// fn take(vault: &mut VaultOf<MYTOKEN>, ids: &BTreeSet<NonFungibleId>) -> BucketOf<MYTOKEN> {
+    ///     vault.take_non_fungibles(ids)
+    /// }
+    /// ```
+    ///
     /// # Panics
     /// Panics if this is not a non-fungible vault or the specified non-fungible resource is not found.
     #[inline(always)]
@@ -77,45 +155,147 @@ impl<RES: Resource> VaultOf<RES> {
         self.inner.take_non_fungibles(non_fungible_ids).unchecked_into()
     }
 
-    /// Creates an ownership proof of this vault.
+    /// Creates an ownership proof of this vault, by non-fungible ID set.
+    ///
+    /// Only for non-fungible (or undeclared kind) resources, for a `fungible` one it doesn't compile:
+    /// ```compile_fail,E0599
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// declare_resource!(MYTOKEN, fungible);
+    ///
+    /// fn prove(vault: &VaultOf<MYTOKEN>, ids: &BTreeSet<NonFungibleId>) -> ProofOf<MYTOKEN> {
+    ///     vault.create_proof_by_ids(ids)
+    /// }
+    /// ```
     #[inline(always)]
-    pub fn create_proof(&self) -> ProofOf<RES> {
-        // self.inner.create_proof().unchecked_into()
-        UncheckedIntoProofOf::unchecked_into(self.inner.create_proof())
+    pub fn create_proof_by_ids(&self, ids: &BTreeSet<NonFungibleId>) -> ProofOf<RES> {
+        UncheckedIntoProofOf::unchecked_into(self.inner.create_proof_by_ids(ids))
     }
 
-    /// Creates an ownership proof of this vault, by amount.
+    /// Returns the ids of all non-fungibles in this vault.
+    ///
+    /// Only for non-fungible (or undeclared kind) resources, for a `fungible` one it doesn't compile:
+    /// ```compile_fail,E0599
+    /// # #[macro_use] extern crate scrypto_statictypes;
+    /// # fn main() {}
+    /// use scrypto::prelude::*;
+    /// use scrypto_statictypes::prelude::*;
+    ///
+    /// declare_resource!(MYTOKEN, fungible);
+    ///
+    /// fn ids(vault: &VaultOf<MYTOKEN>) -> BTreeSet<NonFungibleId> {
+    ///     vault.non_fungible_ids()
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible vault.
     #[inline(always)]
-    pub fn create_proof_by_amount(&self, amount: Decimal) -> ProofOf<RES> {
-        UncheckedIntoProofOf::unchecked_into(self.inner.create_proof_by_amount(amount))
+    pub fn non_fungible_ids(&self) -> BTreeSet<NonFungibleId> {
+        self.inner.non_fungible_ids()
     }
 
-    /// Creates an ownership proof of this vault, by non-fungible ID set.
+    /// Returns all the non-fungible units contained.
+    ///
+    /// When the data type is declared with `declare_resource!(NAME, non_fungible = Data)` any other `T` will not compile.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible vault.
     #[inline(always)]
-    pub fn create_proof_by_ids(&self, ids: &BTreeSet<NonFungibleId>) -> ProofOf<RES> {
-        UncheckedIntoProofOf::unchecked_into(self.inner.create_proof_by_ids(ids))
+    pub fn non_fungibles<T: NonFungibleData>(&self) -> Vec<NonFungible<T>>
+    where RES::Kind: AcceptsNonFungibleData<T> {
+        self.inner.non_fungibles()
     }
 
-    /// Returns the resource definition of resources within this vault.
+    /// Returns a singleton non-fungible.
+    ///
+    /// When the data type is declared with `declare_resource!(NAME, non_fungible = Data)` any other `T` will not compile.
+    ///
+    /// # Panics
+    /// Panics if this is not a singleton vault.
     #[inline(always)]
-    pub fn resource_manager(&self) -> ResourceOf<RES> {
-        self.inner.resource_address().unchecked_into()
+    pub fn non_fungible<T: NonFungibleData>(&self) -> NonFungible<T>
+    where RES::Kind: AcceptsNonFungibleData<T> {
+        self.inner.non_fungible()
+    }
+}
+
+impl<RES: NonFungibleDataResource> VaultOf<RES> {
+    /// Returns the data of all the non-fungibles in this vault, using the declared data type.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible vault.
+    #[inline(always)]
+    pub fn non_fungible_data(&self) -> BTreeMap<NonFungibleId, RES::Data> {
+        self.inner
+            .non_fungibles::<RES::Data>()
+            .into_iter()
+            .map(|non_fungible| (non_fungible.id(), non_fungible.data()))
+            .collect()
+    }
+
+    /// Updates the data of a non-fungible, using the declared data type.
+    ///
+    /// # Panics
+    /// Panics if the non-fungible does not exist or the update is not authorized.
+    #[inline(always)]
+    pub fn update_non_fungible_data(&self, non_fungible_id: &NonFungibleId, data: RES::Data) {
+        self.resource_manager().update_non_fungible_data(non_fungible_id, data)
     }
 }
 
 impl_TryFrom_Slice!(VaultOf<RES>, ParseVaultError);
 
 // VaultOf<RES>::From<Vault>
+#[cfg(feature = "runtime_typechecks")]
+impl<RES: runtimechecks::Resource> TryIntoOf<RES, VaultOf<RES>> for Vault {
+    #[inline(always)]
+    fn try_into_of(self) -> Result<VaultOf<RES>, ConversionError<Self>> {
+        // a new vault (or one from anywhere but the decoded state) may be for whatever resource address was passed in
+        vault_of(self, runtimechecks::BindingSource::Untrusted)
+    }
+}
+
+#[cfg(feature = "runtime_typechecks")]
+fn vault_of<RES: runtimechecks::Resource>(
+    vault: Vault,
+    source: runtimechecks::BindingSource,
+) -> Result<VaultOf<RES>, ConversionError<Vault>> {
+    match runtimechecks::check_address_from::<RES>(vault.resource_address(), source) {
+        Ok(()) => Ok(vault.unchecked_into()),
+        Err(error) => Err(ConversionError { value: vault, error }),
+    }
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: ResourceDecl> TryIntoOf<RES, VaultOf<RES>> for Vault {
+    #[inline(always)]
+    fn try_into_of(self) -> Result<VaultOf<RES>, ConversionError<Self>> {
+        match check_declared_address::<RES>(self.resource_address()) {
+            Ok(()) => Ok(self.unchecked_into()),
+            Err(error) => Err(ConversionError { value: self, error }),
+        }
+    }
+}
+
 #[cfg(feature = "runtime_typechecks")]
 impl<RES: runtimechecks::Resource> From<Vault> for VaultOf<RES> {
     fn from(vault: Vault) -> Self {
-        if !runtimechecks::check_address::<RES>(vault.resource_address()) {
-            // let tmp_bucket =
-            //     ResourceBuilder::new_fungible(DIVISIBILITY_MAXIMUM).initial_supply_fungible(1);
-            // vault.put(tmp_bucket); // this will trigger resource def mismatch error error: Err(InvokeError(Trap(Trap { kind: Host(VaultError(AccountingError(MismatchingResourceManager))) })))
-                                   // shouldn't get here, but just in case (and to help the compiler)
-            panic!("VaultOf mismatch");
-        }
-        vault.unchecked_into()
+        // let tmp_bucket =
+        //     ResourceBuilder::new_fungible(DIVISIBILITY_MAXIMUM).initial_supply_fungible(1);
+        // vault.put(tmp_bucket); // this will trigger resource def mismatch error error: Err(InvokeError(Trap(Trap { kind: Host(VaultError(AccountingError(MismatchingResourceManager))) })))
+        //                        // shouldn't get here, but just in case (and to help the compiler)
+        vault.try_into_of().unwrap_or_else(|e| panic!("VaultOf mismatch: {}", e))
+    }
+}
+
+#[cfg(not(feature = "runtime_typechecks"))]
+impl<RES: ResourceDecl> From<Vault> for VaultOf<RES> {
+    #[inline(always)]
+    fn from(vault: Vault) -> Self {
+        vault.try_into_of().unwrap_or_else(|e| panic!("VaultOf mismatch: {}", e))
     }
 }
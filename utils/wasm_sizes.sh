@@ -0,0 +1,12 @@
+#!/usr/bin/env bash
+# Prints the size of each example's release WASM, ie. to compare the runtime_typechecks overhead between two commits
+set -e
+
+for example in examples/*; do
+    (cd $example;
+        cargo build --quiet --target wasm32-unknown-unknown --release
+        for wasm in target/wasm32-unknown-unknown/release/*.wasm; do
+            echo "$(wc -c < $wasm) $example/$wasm"
+        done
+    )
+done